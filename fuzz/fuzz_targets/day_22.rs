@@ -0,0 +1,8 @@
+#![no_main]
+use advent_of_code_2021::day22::Day22;
+use advent_of_code_2021::solution::solve;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day22>(input, None);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+use advent_of_code_2021::day04::Day04;
+use advent_of_code_2021::solution::solve;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day04>(input, None);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+use advent_of_code_2021::day07::Day07;
+use advent_of_code_2021::solution::solve;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day07>(input, None);
+});
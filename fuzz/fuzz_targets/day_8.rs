@@ -0,0 +1,8 @@
+#![no_main]
+use advent_of_code_2021::day08::Day08;
+use advent_of_code_2021::solution::solve;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day08>(input, None);
+});
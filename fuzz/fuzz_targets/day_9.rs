@@ -0,0 +1,8 @@
+#![no_main]
+use advent_of_code_2021::day09::Day09;
+use advent_of_code_2021::solution::solve;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = solve::<Day09>(input, None);
+});
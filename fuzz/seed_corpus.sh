@@ -0,0 +1,10 @@
+#!/bin/sh
+# Seeds each day's fuzz target with the bundled puzzle input, so that fuzzing starts from a valid
+# input rather than from nothing
+set -e
+cd "$(dirname "$0")"
+for input in ../input/day_*.txt; do
+    target=$(basename "$input" .txt)
+    mkdir -p "corpus/$target"
+    cp "$input" "corpus/$target/input"
+done
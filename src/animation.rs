@@ -0,0 +1,172 @@
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, ClearType};
+use crossterm::{cursor, execute, queue};
+use std::fmt::Display;
+use std::io::{self, Stdout, Write};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_FPS: u32 = 10;
+
+const MAX_FPS: u32 = 120;
+
+const KEYS: &str = "space pause, n step, +/- speed, q quit";
+
+/// Whether to carry on animating after a frame
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Control {
+    Continue,
+    Quit,
+}
+
+/// Plays frames in the terminal at a set frame rate, with a status line showing the frame count.
+/// Space pauses and resumes, `n` or the right arrow steps a frame while paused, `+` and `-`
+/// double and halve the frame rate, and `q` or escape quits. Frames larger than the terminal are
+/// cropped. The terminal is restored when the animation is dropped.
+pub struct Animation {
+    stdout: Stdout,
+    fps: u32,
+    paused: bool,
+    frame: usize,
+    caption: String,
+    content: String,
+}
+
+impl Animation {
+    /// Switches the terminal to a blank screen for the animation
+    pub fn start(fps: u32) -> io::Result<Self> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Self {
+            stdout,
+            fps: fps.clamp(1, MAX_FPS),
+            paused: false,
+            frame: 0,
+            caption: String::new(),
+            content: String::new(),
+        })
+    }
+
+    /// The number of frames shown so far
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Shows a frame with a caption, then waits until the next frame is due, or for as long as
+    /// the animation is paused
+    pub fn show(&mut self, caption: &str, content: &impl Display) -> io::Result<Control> {
+        self.frame += 1;
+        self.caption = caption.to_string();
+        self.content = content.to_string();
+        self.draw()?;
+        self.wait()
+    }
+
+    /// Shows the last frame, and keeps it on screen until the animation is quit
+    pub fn finish(&mut self, caption: &str, content: &impl Display) -> io::Result<()> {
+        self.paused = true;
+        while self.show(caption, content)? == Control::Continue {
+            // stepping past the last frame just shows it again
+            self.frame -= 1;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let status = format!(
+            "frame {} | {} fps{} | {}",
+            self.frame,
+            self.fps,
+            if self.paused { " | paused" } else { "" },
+            KEYS
+        );
+
+        queue!(
+            self.stdout,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(ClearType::All)
+        )?;
+        let lines = IntoIterator::into_iter([status.as_str(), self.caption.as_str()])
+            .chain(self.content.lines())
+            .take(height as usize);
+        for (i, line) in lines.enumerate() {
+            if i > 0 {
+                // raw mode doesn't return the cursor to the start of the line
+                write!(self.stdout, "\r\n")?;
+            }
+            write!(self.stdout, "{}", crop(line, width as usize))?;
+        }
+        self.stdout.flush()
+    }
+
+    fn wait(&mut self) -> io::Result<Control> {
+        let due = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            let timeout = if self.paused {
+                Duration::from_secs(1)
+            } else {
+                match due.checked_duration_since(Instant::now()) {
+                    Some(t) => t,
+                    None => return Ok(Control::Continue),
+                }
+            };
+
+            if !event::poll(timeout)? {
+                continue;
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                Event::Resize(_, _) => {
+                    self.draw()?;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Control::Quit),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(Control::Quit)
+                }
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(Control::Continue),
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('+') | KeyCode::Char('=') => self.fps = (self.fps * 2).min(MAX_FPS),
+                KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+                _ => continue,
+            }
+            self.draw()?;
+        }
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Crops a line to a number of visible characters, keeping any ANSI colour codes
+fn crop(line: &str, width: usize) -> String {
+    let mut cropped = String::with_capacity(line.len());
+    let mut visible = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            cropped.push(c);
+            for c in chars.by_ref() {
+                cropped.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if visible < width {
+            cropped.push(c);
+            visible += 1;
+        }
+    }
+    cropped
+}
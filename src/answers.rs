@@ -0,0 +1,137 @@
+use crate::solution::{Answers, Part};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The default location of the expected answers, in the crate's directory
+pub const DEFAULT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+
+/// The expected answers for each day, read from a TOML file with a table per day, e.g.
+///
+/// ```toml
+/// [day_1]
+/// part_1 = 1766
+/// part_2 = 1797
+/// ```
+///
+/// Answers may be numbers or strings, and multi-line answers such as day 13's code are written
+/// as multi-line strings. A day or part that is missing is not checked.
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    days: BTreeMap<usize, [Option<String>; 2]>,
+}
+
+impl ExpectedAnswers {
+    pub fn read(path: &Path) -> Result<Self, AnswersError> {
+        let s = fs::read_to_string(path).map_err(|e| AnswersError::Read(path.to_path_buf(), e))?;
+        Self::parse(&s)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, AnswersError> {
+        let invalid = |message: String| Err(AnswersError::Invalid(message));
+        let tables: BTreeMap<String, toml::Value> =
+            toml::from_str(s).map_err(|e| AnswersError::Invalid(e.to_string()))?;
+
+        let mut days = BTreeMap::new();
+        for (key, table) in tables {
+            let day = match key.strip_prefix("day_").and_then(|d| d.parse().ok()) {
+                Some(day) => day,
+                None => {
+                    return invalid(format!("expected a table such as [day_1], found [{}]", key))
+                }
+            };
+            let table = match table.as_table() {
+                Some(t) => t,
+                None => return invalid(format!("expected [{}] to be a table", key)),
+            };
+
+            let mut answers = [None, None];
+            for (part, value) in table {
+                let answer = match value {
+                    toml::Value::String(s) => s.trim_end().to_string(),
+                    toml::Value::Integer(i) => i.to_string(),
+                    v => {
+                        return invalid(format!(
+                            "expected {}.{} to be an answer, found {}",
+                            key, part, v
+                        ))
+                    }
+                };
+                match part.as_str() {
+                    "part_1" => answers[0] = Some(answer),
+                    "part_2" => answers[1] = Some(answer),
+                    p => {
+                        return invalid(format!(
+                            "expected part_1 or part_2 in [{}], found {}",
+                            key, p
+                        ))
+                    }
+                }
+            }
+            days.insert(day, answers);
+        }
+
+        Ok(Self { days })
+    }
+
+    /// The expected answer for a day's part, if any
+    pub fn get(&self, day: usize, part: Part) -> Option<&str> {
+        let index = match part {
+            Part::One => 0,
+            Part::Two => 1,
+        };
+        self.days.get(&day)?[index].as_deref()
+    }
+
+    /// Checks each part's answer against the expected answer, ignoring trailing whitespace
+    pub fn check(&self, day: usize, actual: &Answers) -> Vec<(Part, Check)> {
+        actual
+            .parts()
+            .map(|(part, answer)| {
+                let actual = answer.value.trim_end();
+                let check = match self.get(day, part).map(str::trim_end) {
+                    Some(expected) if expected == actual => Check::Pass,
+                    Some(expected) => Check::Fail {
+                        expected: expected.to_string(),
+                        actual: actual.to_string(),
+                    },
+                    None => Check::Unchecked,
+                };
+                (part, check)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Read(PathBuf, io::Error),
+    Invalid(String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Read(path, e) => {
+                write!(f, "could not read answers from {}: {}", path.display(), e)
+            }
+            AnswersError::Invalid(message) => write!(f, "invalid answers: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// The outcome of checking one part of a day against its expected answer
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no expected answer for the part
+    Unchecked,
+}
@@ -0,0 +1,123 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The spread of times taken over a number of runs
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty list of samples
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort_unstable();
+        Self {
+            min: samples[0],
+            // the upper of the two middle samples, when there are an even number
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10}  median {:>10}  max {:>10}",
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.max)
+        )
+    }
+}
+
+/// How long a day took to parse its input and solve each part
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part_1: Stats,
+    pub part_2: Stats,
+}
+
+impl Timings {
+    /// The sum of the median times, as a rough cost of solving the whole day once
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.part_1.median + self.part_2.median
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "parse   {}", self.parse)?;
+        writeln!(f, "part 1  {}", self.part_1)?;
+        writeln!(f, "part 2  {}", self.part_2)
+    }
+}
+
+/// Formats a duration with a unit that keeps it short, e.g. `12.34µs` or `1.20s`
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+/// Parses the input and solves both parts `iterations` times each, timing every run. Parsing is
+/// checked once up front, so a bad input fails before anything is timed.
+pub fn bench<S: Solution>(input: &str, iterations: usize) -> Result<Timings, ParseError> {
+    let iterations = iterations.max(1);
+    let parsed = S::parse(input)?;
+
+    Ok(Timings {
+        parse: time(iterations, || S::parse(input)),
+        part_1: time(iterations, || S::part_1(&parsed)),
+        part_2: time(iterations, || S::part_2(&parsed)),
+    })
+}
+
+fn time<T, F: FnMut() -> T>(iterations: usize, mut f: F) -> Stats {
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect()
+    }
+
+    #[test]
+    fn summarises_an_odd_number_of_samples() {
+        let stats = Stats::from_samples(millis(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn takes_the_upper_middle_sample_of_an_even_number() {
+        let stats = Stats::from_samples(millis(&[4, 1, 3, 2]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(4));
+
+        let stats = Stats::from_samples(millis(&[7, 7]));
+        assert_eq!(stats.median, Duration::from_millis(7));
+    }
+}
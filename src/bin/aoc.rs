@@ -0,0 +1,882 @@
+use advent_of_code_2021::animation::{self, Animation};
+use advent_of_code_2021::answers::{self, Check, ExpectedAnswers};
+use advent_of_code_2021::bench::{format_duration, Timings};
+use advent_of_code_2021::day05::{Day05, Plot};
+use advent_of_code_2021::day06::Day06;
+use advent_of_code_2021::day09::Day09;
+use advent_of_code_2021::day11::Day11;
+use advent_of_code_2021::day13::Day13;
+use advent_of_code_2021::day14::Day14;
+use advent_of_code_2021::day15::Day15;
+use advent_of_code_2021::day23::Day23;
+use advent_of_code_2021::error::ParseError;
+use advent_of_code_2021::generate::{self, Size};
+use advent_of_code_2021::grid::Grid;
+use advent_of_code_2021::image::{self, Format as ImageFormat, Overlay, Rgb};
+use advent_of_code_2021::input::Source;
+use advent_of_code_2021::runner::{self, RunError};
+use advent_of_code_2021::solution::{Answers, Part, PartAnswer, Solution};
+use advent_of_code_2021::PUZZLES;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const USAGE: &str =
+    "usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--format <text|json>]
+                 [--parallel] [--jobs <n>]
+       aoc run 6 --days <n>[,<n>...] [--input <path|->] [--format <text|json>]
+       aoc run <11|14> --steps <n>[,<n>...] [--input <path|->] [--format <text|json>]
+       aoc run 23 --moves [--part <1|2>] [--input <path|->]
+       aoc bench <day|all> [--iterations <n>] [--input <path|->]
+       aoc verify <day|all> [--answers <path>] [--input <path|->]
+       aoc animate <9|11|13|15|23> [--part <1|2>] [--fps <n>] [--input <path|->]
+       aoc image <5|9|13|15> --output <path.ppm|path.pgm|path.svg> [--scale <n>]
+                 [--overlay <low-points|basins|path>]... [--input <path|->]
+       aoc generate <day> [--seed <n>] [--size <n|nxk>]";
+
+const DEFAULT_ITERATIONS: usize = 10;
+
+const ANIMATED_DAYS: [usize; 5] = [9, 11, 13, 15, 23];
+
+const IMAGE_DAYS: [usize; 4] = [5, 9, 13, 15];
+
+/// Images are scaled to be at least this many pixels across, unless a scale is given
+const MIN_IMAGE_SIZE: usize = 400;
+
+/// How a simulation's length is measured, for the days whose parts run a simulation for a fixed
+/// length
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Length {
+    /// Day 6's days
+    Days,
+    /// Day 11's and day 14's steps
+    Steps,
+}
+
+impl Length {
+    fn name(self) -> &'static str {
+        match self {
+            Length::Days => "days",
+            Length::Steps => "steps",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+enum Command {
+    Run {
+        days: Vec<usize>,
+        part: Option<Part>,
+        input: Option<String>,
+        format: Format,
+        /// The number of worker threads to run the days on, if they're run in parallel
+        jobs: Option<usize>,
+        /// Lengths to run the day's simulation for, instead of solving its parts
+        checkpoints: Option<(Length, Vec<usize>)>,
+        /// Whether to print day 23's moves, instead of only the energy they use
+        moves: bool,
+    },
+    Bench {
+        days: Vec<usize>,
+        iterations: usize,
+        input: Option<String>,
+    },
+    Verify {
+        days: Vec<usize>,
+        answers: PathBuf,
+        input: Option<String>,
+    },
+    Animate {
+        day: usize,
+        part: Option<Part>,
+        fps: u32,
+        input: Option<String>,
+    },
+    Image {
+        day: usize,
+        output: PathBuf,
+        format: ImageFormat,
+        scale: Option<usize>,
+        overlays: Vec<Overlay>,
+        input: Option<String>,
+    },
+    Generate {
+        day: usize,
+        seed: Option<u64>,
+        size: Option<Size>,
+    },
+}
+
+impl Command {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut args = args.iter();
+        let command = match args.next().map(String::as_str) {
+            Some(c @ ("run" | "bench" | "verify" | "animate" | "image" | "generate")) => c,
+            Some(c) => return Err(format!("unknown command '{}'", c)),
+            None => return Err("missing command".to_string()),
+        };
+
+        let days = match args.next().map(String::as_str) {
+            Some("all") => (1..=PUZZLES.len()).collect(),
+            Some(d) => match d.parse() {
+                Ok(day) if (1..=PUZZLES.len()).contains(&day) => vec![day],
+                _ => {
+                    return Err(format!(
+                        "day must be 1-{} or 'all', got '{}'",
+                        PUZZLES.len(),
+                        d
+                    ))
+                }
+            },
+            None => return Err("missing day".to_string()),
+        };
+
+        let mut part = None;
+        let mut iterations = DEFAULT_ITERATIONS;
+        let mut answers = PathBuf::from(answers::DEFAULT_PATH);
+        let mut input = None;
+        let mut format = Format::Text;
+        let mut fps = animation::DEFAULT_FPS;
+        let mut output = None;
+        let mut scale = None;
+        let mut overlays = vec![];
+        let mut seed = None;
+        let mut size = None;
+        let mut jobs = None;
+        let mut checkpoints = None;
+        let mut moves = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" if command == "run" || command == "animate" => {
+                    match args.next().map(String::as_str) {
+                        Some("1") => part = Some(Part::One),
+                        Some("2") => part = Some(Part::Two),
+                        Some(p) => return Err(format!("part must be 1 or 2, got '{}'", p)),
+                        None => return Err("missing value for --part".to_string()),
+                    }
+                }
+                "--format" | "-f" if command == "run" => match args.next().map(String::as_str) {
+                    Some("text") => format = Format::Text,
+                    Some("json") => format = Format::Json,
+                    Some(f) => return Err(format!("format must be text or json, got '{}'", f)),
+                    None => return Err("missing value for --format".to_string()),
+                },
+                "--parallel" if command == "run" => {
+                    jobs = jobs
+                        .or_else(|| Some(thread::available_parallelism().map_or(1, |n| n.get())))
+                }
+                "--jobs" | "-j" if command == "run" => match args.next().map(String::as_str) {
+                    Some(j) => match j.parse() {
+                        Ok(j) if j > 0 => jobs = Some(j),
+                        _ => return Err(format!("jobs must be a positive number, got '{}'", j)),
+                    },
+                    None => return Err("missing value for --jobs".to_string()),
+                },
+                l @ ("--days" | "--steps") if command == "run" => {
+                    let length = if l == "--days" {
+                        Length::Days
+                    } else {
+                        Length::Steps
+                    };
+                    match args.next() {
+                        Some(c) => checkpoints = Some((length, parse_checkpoints(length, c)?)),
+                        None => return Err(format!("missing value for {}", l)),
+                    }
+                }
+                "--moves" if command == "run" => moves = true,
+                "--fps" if command == "animate" => match args.next().map(String::as_str) {
+                    Some(f) => match f.parse() {
+                        Ok(f) if f > 0 => fps = f,
+                        _ => return Err(format!("fps must be a positive number, got '{}'", f)),
+                    },
+                    None => return Err("missing value for --fps".to_string()),
+                },
+                "--output" | "-o" if command == "image" => match args.next() {
+                    Some(o) => output = Some(PathBuf::from(o)),
+                    None => return Err("missing value for --output".to_string()),
+                },
+                "--scale" if command == "image" => match args.next().map(String::as_str) {
+                    Some(s) => match s.parse() {
+                        Ok(s) if s > 0 => scale = Some(s),
+                        _ => return Err(format!("scale must be a positive number, got '{}'", s)),
+                    },
+                    None => return Err("missing value for --scale".to_string()),
+                },
+                "--overlay" if command == "image" => match args.next().map(String::as_str) {
+                    Some("low-points") => overlays.push(Overlay::LowPoints),
+                    Some("basins") => overlays.push(Overlay::Basins),
+                    Some("path") => overlays.push(Overlay::Path),
+                    Some(o) => {
+                        return Err(format!(
+                            "overlay must be low-points, basins or path, got '{}'",
+                            o
+                        ))
+                    }
+                    None => return Err("missing value for --overlay".to_string()),
+                },
+                "--iterations" | "-n" if command == "bench" => {
+                    match args.next().map(String::as_str) {
+                        Some(n) => match n.parse() {
+                            Ok(n) if n > 0 => iterations = n,
+                            _ => {
+                                return Err(format!(
+                                    "iterations must be a positive number, got '{}'",
+                                    n
+                                ))
+                            }
+                        },
+                        None => return Err("missing value for --iterations".to_string()),
+                    }
+                }
+                "--answers" | "-a" if command == "verify" => match args.next() {
+                    Some(a) => answers = PathBuf::from(a),
+                    None => return Err("missing value for --answers".to_string()),
+                },
+                "--seed" if command == "generate" => match args.next().map(String::as_str) {
+                    Some(s) => match s.parse() {
+                        Ok(s) => seed = Some(s),
+                        _ => return Err(format!("seed must be a number, got '{}'", s)),
+                    },
+                    None => return Err("missing value for --seed".to_string()),
+                },
+                "--size" if command == "generate" => match args.next() {
+                    Some(s) => size = Some(s.parse()?),
+                    None => return Err("missing value for --size".to_string()),
+                },
+                "--input" | "-i" if command != "generate" => match args.next() {
+                    Some(i) => input = Some(i.clone()),
+                    None => return Err("missing value for --input".to_string()),
+                },
+                a => return Err(format!("unexpected argument '{}'", a)),
+            }
+        }
+
+        if input.is_some() && days.len() > 1 {
+            return Err("--input can only be used with a single day".to_string());
+        }
+        if input.is_some() && jobs.is_some() {
+            return Err("--input can't be used when running in parallel".to_string());
+        }
+        if let Some((length, _)) = &checkpoints {
+            let allowed = match length {
+                Length::Days => days == [6],
+                Length::Steps => days == [11] || days == [14],
+            };
+            if !allowed {
+                return Err(format!(
+                    "--{} can only be used with day {}",
+                    length.name(),
+                    if *length == Length::Days {
+                        "6"
+                    } else {
+                        "11 or 14"
+                    }
+                ));
+            }
+            if part.is_some() || jobs.is_some() {
+                return Err(format!(
+                    "--{} can't be used with --part or in parallel",
+                    length.name()
+                ));
+            }
+        }
+
+        if moves {
+            if days != [23] {
+                return Err("--moves can only be used with day 23".to_string());
+            }
+            if format == Format::Json || jobs.is_some() || checkpoints.is_some() {
+                return Err("--moves can't be used with --format json or in parallel".to_string());
+            }
+        }
+
+        Ok(match command {
+            "run" => Command::Run {
+                days,
+                part,
+                input,
+                format,
+                jobs,
+                checkpoints,
+                moves,
+            },
+            "bench" => Command::Bench {
+                days,
+                iterations,
+                input,
+            },
+            "verify" => Command::Verify {
+                days,
+                answers,
+                input,
+            },
+            "generate" => match days[..] {
+                [day] => Command::Generate { day, seed, size },
+                _ => return Err("only one day can be generated at a time".to_string()),
+            },
+            "image" => {
+                let day = match days[..] {
+                    [day] if IMAGE_DAYS.contains(&day) => day,
+                    _ => {
+                        return Err(format!(
+                            "only days {:?} can be drawn, one at a time",
+                            IMAGE_DAYS
+                        ))
+                    }
+                };
+                let output = output.ok_or("missing --output")?;
+                let format = ImageFormat::from_path(&output)
+                    .ok_or("output must end in .ppm, .pgm or .svg")?;
+                for overlay in &overlays {
+                    let allowed = match overlay {
+                        Overlay::LowPoints | Overlay::Basins => day == 9,
+                        Overlay::Path => day == 15,
+                    };
+                    if !allowed {
+                        return Err(format!(
+                            "day {} has no {} overlay",
+                            day,
+                            overlay_name(overlay)
+                        ));
+                    }
+                }
+
+                Command::Image {
+                    day,
+                    output,
+                    format,
+                    scale,
+                    overlays,
+                    input,
+                }
+            }
+            _ => match days[..] {
+                [day] if ANIMATED_DAYS.contains(&day) => {
+                    if part.is_some() && day != 23 {
+                        return Err("only day 23 can be animated by part".to_string());
+                    }
+                    Command::Animate {
+                        day,
+                        part,
+                        fps,
+                        input,
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "only days {:?} can be animated, one at a time",
+                        ANIMATED_DAYS
+                    ))
+                }
+            },
+        })
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match Command::parse(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match command {
+        Command::Run {
+            days,
+            input,
+            format,
+            checkpoints: Some((length, at)),
+            ..
+        } => {
+            let day = days[0];
+            let results = Source::new(day, input.as_deref())
+                .read()
+                .map_err(|e| e.to_string())
+                .and_then(|i| simulate(day, &i, &at).map_err(|e| e.to_string()));
+            let results = results.unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            });
+
+            match format {
+                Format::Text => {
+                    println!("day {}", day);
+                    for (at, answer) in results {
+                        match answer {
+                            Some(a) => println!("after {} {}: {}", at, length.name(), a),
+                            None => println!("after {} {}: too many to count", at, length.name()),
+                        }
+                    }
+                }
+                Format::Json => {
+                    let json: Vec<_> = results
+                        .into_iter()
+                        .map(|(at, answer)| {
+                            let answer =
+                                answer.map_or("null".to_string(), |a| json_string(&a.to_string()));
+                            format!(
+                                "{{\"day\": {}, \"{}\": {}, \"answer\": {}}}",
+                                day,
+                                length.name(),
+                                at,
+                                answer
+                            )
+                        })
+                        .collect();
+                    println!("[\n  {}\n]", json.join(",\n  "));
+                }
+            }
+        }
+        Command::Run {
+            part,
+            input,
+            moves: true,
+            ..
+        } => {
+            let result = Source::new(23, input.as_deref())
+                .read()
+                .map_err(|e| e.to_string())
+                .and_then(|i| print_moves(part, &i, &mut io::stdout().lock()));
+
+            if let Err(e) = result {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Run {
+            days,
+            part,
+            input,
+            format,
+            jobs,
+            checkpoints: None,
+            moves: false,
+        } => {
+            let mut failed = false;
+            let mut json = vec![];
+            let mut report = |day: usize, answers: Result<Answers, RunError>| {
+                if format == Format::Text {
+                    println!("day {}", day);
+                }
+                match answers {
+                    Ok(a) if format == Format::Json => {
+                        json.extend(a.parts().map(|(part, answer)| to_json(day, part, answer)))
+                    }
+                    Ok(a) => print!("{}", a),
+                    Err(e) => {
+                        eprintln!("error: {}", e);
+                        failed = true;
+                    }
+                }
+            };
+
+            match jobs {
+                Some(jobs) => {
+                    let start = Instant::now();
+                    for (day, answers) in runner::run_parallel(&days, part, jobs) {
+                        report(day, answers);
+                    }
+                    if format == Format::Text {
+                        println!(
+                            "\nran {} days on {} workers in {}",
+                            days.len(),
+                            jobs.min(days.len()),
+                            format_duration(start.elapsed())
+                        );
+                    }
+                }
+                None => {
+                    for day in days {
+                        report(
+                            day,
+                            runner::run_day(day, &Source::new(day, input.as_deref()), part),
+                        );
+                    }
+                }
+            }
+
+            if format == Format::Json {
+                println!("[\n  {}\n]", json.join(",\n  "));
+            }
+
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            days,
+            iterations,
+            input,
+        } => {
+            let mut failed = false;
+            let mut results = vec![];
+            for day in days {
+                println!("day {}", day);
+                let timings = Source::new(day, input.as_deref())
+                    .read()
+                    .map_err(|e| e.to_string())
+                    .and_then(|i| {
+                        PUZZLES[day - 1]
+                            .bench(&i, iterations)
+                            .map_err(|e| e.to_string())
+                    });
+
+                match timings {
+                    Ok(t) => {
+                        print!("{}", t);
+                        results.push((day, t));
+                    }
+                    Err(e) => {
+                        eprintln!("error: {}", e);
+                        failed = true;
+                    }
+                }
+            }
+
+            println!();
+            print_summary(&results, iterations);
+
+            if failed {
+                process::exit(1);
+            }
+        }
+        Command::Verify {
+            days,
+            answers,
+            input,
+        } => {
+            let expected = ExpectedAnswers::read(&answers).unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            });
+
+            let (mut passed, mut failed, mut unchecked) = (0, 0, 0);
+            let mut errors = false;
+            for day in days {
+                let answers = runner::run_day(day, &Source::new(day, input.as_deref()), None);
+                let answers = match answers {
+                    Ok(a) => a,
+                    Err(e) => {
+                        eprintln!("day {}: error: {}", day, e);
+                        errors = true;
+                        // the parts with an expected answer fail, and the rest stay unchecked
+                        for part in [Part::One, Part::Two] {
+                            match expected.get(day, part) {
+                                Some(_) => failed += 1,
+                                None => unchecked += 1,
+                            }
+                        }
+                        continue;
+                    }
+                };
+
+                for (part, check) in expected.check(day, &answers) {
+                    match check {
+                        Check::Pass => {
+                            println!("day {} part {}: ok", day, part);
+                            passed += 1;
+                        }
+                        Check::Fail { expected, actual } => {
+                            println!("day {} part {}: FAILED", day, part);
+                            println!("  expected:{}", indent(&expected));
+                            println!("  actual:  {}", indent(&actual));
+                            failed += 1;
+                        }
+                        Check::Unchecked => {
+                            println!("day {} part {}: no expected answer", day, part);
+                            unchecked += 1;
+                        }
+                    }
+                }
+            }
+
+            println!(
+                "\n{} passed, {} failed, {} unchecked",
+                passed, failed, unchecked
+            );
+
+            if failed > 0 || errors {
+                process::exit(1);
+            }
+        }
+        Command::Animate {
+            day,
+            part,
+            fps,
+            input,
+        } => {
+            let result = Source::new(day, input.as_deref())
+                .read()
+                .map_err(|e| e.to_string())
+                .and_then(|i| animate(day, part, &i, fps));
+
+            if let Err(e) = result {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Image {
+            day,
+            output,
+            format,
+            scale,
+            overlays,
+            input,
+        } => {
+            let result = Source::new(day, input.as_deref())
+                .read()
+                .map_err(|e| e.to_string())
+                .and_then(|i| draw(day, &i, &overlays).map_err(|e| e.to_string()))
+                .and_then(|img| {
+                    let size = img.width().max(img.height()).max(1);
+                    let scale = scale.unwrap_or_else(|| (MIN_IMAGE_SIZE / size).max(1));
+                    File::create(&output)
+                        .and_then(|f| image::write(&img, format, scale, &mut BufWriter::new(f)))
+                        .map_err(|e| format!("could not write {}: {}", output.display(), e))
+                });
+
+            if let Err(e) = result {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Generate { day, seed, size } => {
+            // a random seed is reported, so that an interesting input can be generated again
+            let seed = seed.unwrap_or_else(|| {
+                let seed = rand::random::<u32>() as u64;
+                eprintln!("seed {}", seed);
+                seed
+            });
+            println!("{}", generate::generate(day, seed, size).unwrap());
+        }
+    }
+}
+
+fn overlay_name(overlay: &Overlay) -> &'static str {
+    match overlay {
+        Overlay::LowPoints => "low-points",
+        Overlay::Basins => "basins",
+        Overlay::Path => "path",
+    }
+}
+
+/// Parses a comma separated list of lengths to run a simulation for
+fn parse_checkpoints(length: Length, list: &str) -> Result<Vec<usize>, String> {
+    list.split(',')
+        .map(|c| {
+            c.parse().map_err(|_| {
+                format!(
+                    "{} must be a list of numbers, e.g. 80,256, got '{}'",
+                    length.name(),
+                    list
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parses a day's input, then runs its simulation for each of a number of days or steps. Day 6
+/// counts the fish, day 11 the total flashes and day 14 subtracts the element counts.
+fn simulate(
+    day: usize,
+    input: &str,
+    at: &[usize],
+) -> Result<Vec<(usize, Option<usize>)>, ParseError> {
+    Ok(match day {
+        6 => Day06::parse(input)?.counts_after(at),
+        11 => Day11::parse(input)?
+            .flashes_after(at)
+            .into_iter()
+            .map(|(step, flashes)| (step, Some(flashes)))
+            .collect(),
+        14 => Day14::parse(input)?.outputs_after(at),
+        _ => unreachable!("day {} has no simulation", day),
+    })
+}
+
+/// Parses a day's input and draws it. Day 5 is drawn with all its lines, and day 13 once folded.
+fn draw(day: usize, input: &str, overlays: &[Overlay]) -> Result<Grid<Rgb>, ParseError> {
+    Ok(match day {
+        5 => Plot::new(&mut Day05::parse(input)?).image(),
+        9 => Day09::parse(input)?.image(overlays),
+        13 => Day13::part_2(&Day13::parse(input)?).image(),
+        15 => Day15::parse(input)?.image(overlays),
+        _ => unreachable!("day {} has no image", day),
+    })
+}
+
+/// Parses a day's input, then plays its animation until it is quit. Day 23 animates the
+/// unfolded burrow of part 2 unless part 1 is asked for.
+fn animate(day: usize, part: Option<Part>, input: &str, fps: u32) -> Result<(), String> {
+    let result = match day {
+        9 => {
+            let map = Day09::parse(input).map_err(|e| e.to_string())?;
+            Animation::start(fps).and_then(|mut a| map.animate(&mut a))
+        }
+        11 => {
+            let grid = Day11::parse(input).map_err(|e| e.to_string())?;
+            Animation::start(fps).and_then(|mut a| grid.animate(&mut a))
+        }
+        13 => {
+            let (grid, instructions) = Day13::parse(input).map_err(|e| e.to_string())?;
+            Animation::start(fps).and_then(|mut a| grid.animate(&instructions, &mut a))
+        }
+        15 => {
+            let grid = Day15::parse(input).map_err(|e| e.to_string())?;
+            Animation::start(fps).and_then(|mut a| grid.animate(&(0, 0), &mut a))
+        }
+        23 => {
+            let burrow = Day23::parse(input).map_err(|e| e.to_string())?;
+            let burrow = match part {
+                Some(Part::One) => burrow,
+                _ => burrow.unfold(),
+            };
+            Animation::start(fps).and_then(|mut a| burrow.animate(&mut a))
+        }
+        _ => unreachable!("day {} has no animation", day),
+    };
+
+    result.map_err(|e| format!("could not animate day {}: {}", day, e))
+}
+
+/// Parses day 23's input, then prints the moves that organize the burrow of each part, or only
+/// the given part
+fn print_moves(part: Option<Part>, input: &str, out: &mut impl Write) -> Result<(), String> {
+    let burrow = Day23::parse(input).map_err(|e| e.to_string())?;
+    let parts = [(Part::One, burrow), (Part::Two, burrow.unfold())];
+    let parts = parts
+        .iter()
+        .filter(|(p, _)| part.is_none() || part == Some(*p));
+    for (i, (p, burrow)) in parts.enumerate() {
+        let separator = if i > 0 { "\n" } else { "" };
+        match writeln!(out, "{}part {}", separator, p).and_then(|_| burrow.write_moves(out)) {
+            Ok(()) => {}
+            // the reader has all it wants, such as `head`
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(format!("could not print the moves: {}", e)),
+        }
+    }
+    Ok(())
+}
+
+/// Writes a part's answer as a JSON object, with any extras alongside the answer
+fn to_json(day: usize, part: Part, answer: &PartAnswer) -> String {
+    let mut fields = vec![
+        format!("\"day\": {}", day),
+        format!("\"part\": {}", part),
+        format!("\"answer\": {}", json_string(&answer.value)),
+    ];
+    fields.extend(
+        answer
+            .extras
+            .iter()
+            .map(|(name, value)| format!("{}: {}", json_string(name), value)),
+    );
+    fields.push(format!("\"elapsed_ns\": {}", answer.elapsed.as_nanos()));
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Puts an answer after its label, or each line of a multi-line answer on its own indented line
+fn indent(answer: &str) -> String {
+    if answer.contains('\n') {
+        answer.lines().map(|l| format!("\n    {}", l)).collect()
+    } else {
+        format!(" {}", answer)
+    }
+}
+
+/// Prints the median times of every day that was benchmarked, with a total row
+fn print_summary(results: &[(usize, Timings)], iterations: usize) {
+    println!("median of {} iterations", iterations);
+    println!(
+        "{:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+
+    let mut totals = [Duration::ZERO; 4];
+    for (day, t) in results {
+        let row = [t.parse.median, t.part_1.median, t.part_2.median, t.total()];
+        for (total, time) in totals.iter_mut().zip(row) {
+            *total += time;
+        }
+        print_row(&day.to_string(), row);
+    }
+    print_row("all", totals);
+}
+
+fn print_row(label: &str, times: [Duration; 4]) {
+    let [parse, part_1, part_2, total] = times.map(format_duration);
+    println!(
+        "{:>5}  {:>10}  {:>10}  {:>10}  {:>10}",
+        label, parse, part_1, part_2, total
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    /// Takes a number of bytes, then fails every write with an error
+    struct Failing(usize, io::ErrorKind);
+
+    impl Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            match self.0.checked_sub(buf.len()) {
+                Some(left) => {
+                    self.0 = left;
+                    Ok(buf.len())
+                }
+                None => Err(self.1.into()),
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stops_printing_moves_when_the_reader_goes_away() {
+        let mut out = Failing(100, io::ErrorKind::BrokenPipe);
+        assert_eq!(print_moves(None, EXAMPLE, &mut out), Ok(()));
+
+        let mut out = Failing(100, io::ErrorKind::Other);
+        let error = print_moves(Some(Part::One), EXAMPLE, &mut out).unwrap_err();
+        assert!(error.starts_with("could not print the moves: "));
+    }
+}
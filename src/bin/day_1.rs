@@ -1,21 +1,6 @@
-const INPUT: &str = include_str!("../../input/day_1.txt");
+use advent_of_code_2021::day01::Day01;
+use advent_of_code_2021::solution;
 
 fn main() {
-    let depths: Vec<i32> = INPUT
-        .lines()
-        .filter_map(|l| l.trim().parse().ok())
-        .collect();
-
-    let increasing = depths.windows(2).filter(|w| w[1] > w[0]);
-
-    println!("increasing depths: {}", increasing.count());
-
-    let window_sums = depths
-        .windows(3)
-        .map(|w| w.iter().sum())
-        .collect::<Vec<i32>>();
-
-    let windows = window_sums.windows(2).filter(|w| w[1] > w[0]);
-
-    println!("increasing windows: {}", windows.count());
+    solution::main::<Day01>()
 }
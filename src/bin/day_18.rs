@@ -0,0 +1,6 @@
+use advent_of_code_2021::day18::Day18;
+use advent_of_code_2021::solution;
+
+fn main() {
+    solution::main::<Day18>()
+}
@@ -0,0 +1,6 @@
+use advent_of_code_2021::day21::Day21;
+use advent_of_code_2021::solution;
+
+fn main() {
+    solution::main::<Day21>()
+}
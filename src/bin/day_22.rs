@@ -0,0 +1,6 @@
+use advent_of_code_2021::day22::Day22;
+use advent_of_code_2021::solution;
+
+fn main() {
+    solution::main::<Day22>()
+}
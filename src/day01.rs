@@ -0,0 +1,64 @@
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: usize = 1;
+    type Input = Vec<i32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, |l| parse_token(Self::DAY, l, l.trim(), "a depth"))
+    }
+
+    fn part_1(depths: &Self::Input) -> Self::Part1 {
+        depths.windows(2).filter(|w| w[1] > w[0]).count()
+    }
+
+    fn part_2(depths: &Self::Input) -> Self::Part2 {
+        // three depths can add up to more than fits in an i32
+        let window_sums = depths
+            .windows(3)
+            .map(|w| w.iter().map(|&d| d as i64).sum())
+            .collect::<Vec<i64>>();
+
+        window_sums.windows(2).filter(|w| w[1] > w[0]).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+    #[test]
+    fn part_1_counts_depth_increases() {
+        let depths = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part_1(&depths), 7);
+    }
+
+    #[test]
+    fn part_2_counts_window_sum_increases() {
+        let depths = Day01::parse(EXAMPLE).unwrap();
+        assert_eq!(Day01::part_2(&depths), 5);
+    }
+
+    #[test]
+    fn sums_windows_of_the_deepest_depths() {
+        let depths = Day01::parse("2147483646\n2147483647\n2147483647\n2147483647").unwrap();
+        assert_eq!(Day01::part_2(&depths), 1);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::day02::Direction::{Down, Forward, Up};
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::solution::{Checked, Solution};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum Direction {
+    Forward(i32),
+    Down(i32),
+    Up(i32),
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, value) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(Day02::DAY, s, s, "a direction and a value"))?;
+        let value = parse_token(Day02::DAY, s, value, "a number")?;
+        match direction {
+            "forward" => Ok(Self::Forward(value)),
+            "down" => Ok(Self::Down(value)),
+            "up" => Ok(Self::Up(value)),
+            _ => Err(ParseError::new(
+                Day02::DAY,
+                s,
+                direction,
+                "'forward', 'down' or 'up'",
+            )),
+        }
+    }
+}
+
+#[derive(Default, Copy, Clone)]
+pub struct Position {
+    horizontal: i64,
+    depth: i64,
+    aim: i64,
+}
+
+impl Position {
+    /// Moves as part 1 describes, or returns `None` if the position would be too far to count
+    pub fn part_1(&mut self, direction: &Direction) -> Option<()> {
+        match *direction {
+            Forward(i) => self.horizontal = self.horizontal.checked_add(i as i64)?,
+            Down(i) => self.depth = self.depth.checked_add(i as i64)?,
+            Up(i) => self.depth = self.depth.checked_sub(i as i64)?,
+        }
+        Some(())
+    }
+
+    /// Moves as part 2 describes, or returns `None` if the position would be too far to count
+    pub fn part_2(&mut self, direction: &Direction) -> Option<()> {
+        match *direction {
+            Forward(i) => {
+                self.horizontal = self.horizontal.checked_add(i as i64)?;
+                self.depth = self.depth.checked_add(self.aim.checked_mul(i as i64)?)?;
+            }
+            Down(i) => self.aim = self.aim.checked_add(i as i64)?,
+            Up(i) => self.aim = self.aim.checked_sub(i as i64)?,
+        }
+        Some(())
+    }
+
+    pub fn reset(&mut self) {
+        self.horizontal = 0;
+        self.depth = 0;
+        self.aim = 0;
+    }
+
+    /// The horizontal position multiplied by the depth, or `None` if it's too large to count
+    pub fn value(&self) -> Option<i64> {
+        self.horizontal.checked_mul(self.depth)
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: usize = 2;
+    type Input = Vec<Direction>;
+    type Part1 = Checked<i64>;
+    type Part2 = Checked<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, Direction::from_str)
+    }
+
+    fn part_1(directions: &Self::Input) -> Self::Part1 {
+        let mut position = Position::default();
+
+        directions
+            .iter()
+            .try_for_each(|d| position.part_1(d))
+            .and_then(|_| position.value())
+            .into()
+    }
+
+    fn part_2(directions: &Self::Input) -> Self::Part2 {
+        let mut position = Position::default();
+
+        directions
+            .iter()
+            .try_for_each(|d| position.part_2(d))
+            .and_then(|_| position.value())
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn part_1_multiplies_horizontal_position_and_depth() {
+        let directions = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part_1(&directions), Checked::Answer(150));
+    }
+
+    #[test]
+    fn part_2_uses_aim() {
+        let directions = Day02::parse(EXAMPLE).unwrap();
+        assert_eq!(Day02::part_2(&directions), Checked::Answer(900));
+    }
+
+    #[test]
+    fn counts_courses_beyond_32_bits() {
+        let directions = Day02::parse("forward 2000000000\nforward 2000000000").unwrap();
+        assert_eq!(Day02::part_1(&directions), Checked::Answer(0));
+        let directions = Day02::parse("forward 2000000000\ndown 2000000000").unwrap();
+        assert_eq!(
+            Day02::part_1(&directions),
+            Checked::Answer(4000000000000000000)
+        );
+    }
+
+    #[test]
+    fn answers_overflow_for_courses_too_far_to_count() {
+        let directions = Day02::parse(
+            "forward 2000000000\nforward 2000000000\ndown 2000000000\ndown 2000000000",
+        )
+        .unwrap();
+        assert_eq!(Day02::part_1(&directions), Checked::Overflow);
+        assert_eq!(Day02::part_1(&directions).to_string(), "overflow");
+
+        // part 1 ends up level, but part 2's depth keeps growing with the aim
+        let directions = Day02::parse(
+            "down 2000000000\nforward 2000000000\nforward 2000000000\nforward 2000000000\n\
+             up 2000000000",
+        )
+        .unwrap();
+        assert_eq!(Day02::part_1(&directions), Checked::Answer(0));
+        assert_eq!(Day02::part_2(&directions), Checked::Overflow);
+    }
+}
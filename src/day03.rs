@@ -0,0 +1,292 @@
+use crate::error::ParseError;
+use crate::solution::{Checked, Solution};
+use std::ops::Index;
+use std::str::FromStr;
+
+/// The most binary digits a value can have, as values are converted to `usize`. Multiplying two
+/// values can still overflow, which the parts answer as [Checked::Overflow].
+pub const MAX_BITS: usize = usize::BITS as usize;
+
+#[derive(Debug, Clone)]
+struct BinaryVec(Vec<bool>);
+
+impl From<Vec<bool>> for BinaryVec {
+    fn from(b: Vec<bool>) -> Self {
+        Self(b)
+    }
+}
+
+impl From<BinaryVec> for usize {
+    fn from(b: BinaryVec) -> Self {
+        let s: String = b.0.iter().map(|b| if *b { '1' } else { '0' }).collect();
+        usize::from_str_radix(s.as_ref(), 2).unwrap()
+    }
+}
+
+impl Index<usize> for BinaryVec {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl BinaryVec {
+    pub fn invert(&mut self) {
+        for b in self.0.iter_mut() {
+            *b = !*b;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticReport {
+    values: Vec<BinaryVec>,
+    gamma: Option<usize>,
+    epsilon: Option<usize>,
+    oxygen: Option<usize>,
+    co2: Option<usize>,
+}
+
+impl DiagnosticReport {
+    pub fn gamma_rate(&mut self) -> usize {
+        if self.gamma.is_none() {
+            let gamma = self.gamma();
+            self.gamma = Some(gamma.into());
+        }
+
+        self.gamma.unwrap()
+    }
+
+    pub fn epsilon_rate(&mut self) -> usize {
+        if self.epsilon.is_none() {
+            let mut gamma = self.gamma();
+            gamma.invert();
+            self.epsilon = Some(gamma.into());
+        }
+
+        self.epsilon.unwrap()
+    }
+
+    pub fn oxygen_generator_rating(&mut self) -> usize {
+        if self.oxygen.is_none() {
+            let mut oxygen_generator_report = self.values.clone();
+            let len = oxygen_generator_report[0].len();
+            for i in 0..len {
+                if oxygen_generator_report.len() == 1 {
+                    break;
+                }
+
+                let count_zeros_ones = Self::count_zeros_ones(&oxygen_generator_report);
+                let s = Self::calculate_binary(&count_zeros_ones[i..i + 1], |zeros, ones| {
+                    ones >= zeros
+                });
+                oxygen_generator_report.retain(|r| r[i] == s[0]);
+            }
+
+            self.oxygen = Some(oxygen_generator_report[0].clone().into());
+        }
+
+        self.oxygen.unwrap()
+    }
+
+    pub fn co2_scrubber_rating(&mut self) -> usize {
+        if self.co2.is_none() {
+            let mut co2_scrubber_report = self.values.clone();
+            let len = co2_scrubber_report[0].len();
+            for i in 0..len {
+                if co2_scrubber_report.len() > 1 {
+                    let count_zeros_ones = Self::count_zeros_ones(&co2_scrubber_report);
+                    // when every value has the same bit, keeping the least common would keep none
+                    if count_zeros_ones[i].contains(&0) {
+                        continue;
+                    }
+                    let s = Self::calculate_binary(&count_zeros_ones[i..i + 1], |zeros, ones| {
+                        zeros > ones
+                    });
+                    co2_scrubber_report.retain(|r| r[i] == s[0]);
+                }
+            }
+
+            self.co2 = Some(co2_scrubber_report[0].clone().into());
+        }
+
+        self.co2.unwrap()
+    }
+
+    fn calculate_binary<F: Fn(usize, usize) -> bool>(counts: &[Vec<usize>], f: F) -> BinaryVec {
+        counts
+            .iter()
+            .map(|t| f(t[0], t[1]))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn gamma(&self) -> BinaryVec {
+        let count_zeros_ones = Self::count_zeros_ones(&self.values);
+        Self::calculate_binary(&count_zeros_ones, |zeros, ones| ones > zeros)
+    }
+
+    fn count_zeros_ones(values: &[BinaryVec]) -> Vec<Vec<usize>> {
+        let len = values[0].len();
+        let mut counts = vec![vec![0; 2]; len];
+        for i in 0..len {
+            let bools: Vec<bool> = values.iter().map(|f| f[i]).collect();
+            for b in bools {
+                counts[i][if b { 1 } else { 0 }] += 1;
+            }
+        }
+        counts
+    }
+}
+
+impl FromStr for DiagnosticReport {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values: Vec<BinaryVec> = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let error = |token, expected: &str| {
+                ParseError::new(Day03::DAY, line, token, expected).on_line(i + 1)
+            };
+
+            if line.is_empty() || line.len() > MAX_BITS {
+                let expected = format!("between 1 and {} binary digits", MAX_BITS);
+                return Err(error(line, &expected));
+            }
+
+            if let Some(first) = values.first() {
+                if line.len() != first.len() {
+                    return Err(error(line, &format!("{} binary digits", first.len())));
+                }
+            }
+
+            let mut bits = Vec::with_capacity(line.len());
+            for (j, c) in line.char_indices() {
+                match c {
+                    '0' => bits.push(false),
+                    '1' => bits.push(true),
+                    _ => return Err(error(&line[j..j + c.len_utf8()], "'0' or '1'")),
+                }
+            }
+            values.push(bits.into());
+        }
+
+        if values.is_empty() {
+            return Err(ParseError::new(Day03::DAY, s, s, "a diagnostic report"));
+        }
+
+        Ok(Self {
+            values,
+            gamma: None,
+            epsilon: None,
+            oxygen: None,
+            co2: None,
+        })
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: usize = 3;
+    type Input = DiagnosticReport;
+    type Part1 = Checked<usize>;
+    type Part2 = Checked<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        DiagnosticReport::from_str(input)
+    }
+
+    fn part_1(diagnostic_report: &Self::Input) -> Self::Part1 {
+        let mut diagnostic_report = diagnostic_report.clone();
+        let gamma_rate = diagnostic_report.gamma_rate();
+        gamma_rate
+            .checked_mul(diagnostic_report.epsilon_rate())
+            .into()
+    }
+
+    fn part_2(diagnostic_report: &Self::Input) -> Self::Part2 {
+        let mut diagnostic_report = diagnostic_report.clone();
+        let oxygen_generator_rating = diagnostic_report.oxygen_generator_rating();
+        oxygen_generator_rating
+            .checked_mul(diagnostic_report.co2_scrubber_rating())
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+    #[test]
+    fn part_1_multiplies_gamma_and_epsilon_rates() {
+        let mut report = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(report.gamma_rate(), 22);
+        assert_eq!(report.epsilon_rate(), 9);
+        assert_eq!(Day03::part_1(&report), Checked::Answer(198));
+    }
+
+    #[test]
+    fn part_2_multiplies_oxygen_and_co2_ratings() {
+        let mut report = Day03::parse(EXAMPLE).unwrap();
+        assert_eq!(report.oxygen_generator_rating(), 23);
+        assert_eq!(report.co2_scrubber_rating(), 10);
+        assert_eq!(Day03::part_2(&report), Checked::Answer(230));
+    }
+
+    #[test]
+    fn co2_rating_skips_bits_that_every_value_shares() {
+        let mut report = Day03::parse("10\n11").unwrap();
+        assert_eq!(report.co2_scrubber_rating(), 2);
+    }
+
+    #[test]
+    fn multiplies_values_up_to_64_bits() {
+        let (high, low) = (
+            "1".repeat(16) + &"0".repeat(16),
+            "0".repeat(16) + &"1".repeat(16),
+        );
+        let report = Day03::parse(&format!("{}\n{}\n{}", high, high, low)).unwrap();
+        assert_eq!(
+            Day03::part_1(&report),
+            Checked::Answer(0xffff_0000 * 0xffff)
+        );
+        assert_eq!(
+            Day03::part_2(&report),
+            Checked::Answer(0xffff_0000 * 0xffff)
+        );
+
+        let (high, low) = ("1".repeat(32) + &"0".repeat(32), "0".repeat(64));
+        let report = Day03::parse(&format!("{}\n{}\n{}", high, high, low)).unwrap();
+        assert_eq!(Day03::part_1(&report), Checked::Overflow);
+        assert_eq!(Day03::part_2(&report), Checked::Answer(0));
+
+        let value = "1".repeat(65);
+        assert_eq!(
+            Day03::parse(&value).unwrap_err().to_string(),
+            format!(
+                "day 3, line 1, column 1: expected between 1 and 64 binary digits, found '{}'",
+                value
+            )
+        );
+    }
+}
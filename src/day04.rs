@@ -0,0 +1,341 @@
+use crate::error::{parse_token, ParseError};
+use crate::solution::{Answer, Checked, Solution};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Bingo {
+    numbers: Vec<usize>,
+    last_number_index: usize,
+    boards: Vec<Board>,
+}
+
+impl Bingo {
+    /// The boards that have won so far, in the order they won, or `None` if none have
+    pub fn winning_boards(&self) -> Option<Vec<Checked<WinningBoard>>> {
+        let mut winners = vec![];
+        for (i, board) in self.boards.iter().enumerate() {
+            if let Some((number, index)) = board.winning_number_and_index() {
+                winners.push((i, board, number, index));
+            }
+        }
+
+        if !winners.is_empty() {
+            winners.sort_by_key(|&(_, _, _, index)| index);
+            let winning_boards = winners.into_iter().map(|(i, board, number, index)| {
+                board
+                    .score(number)
+                    .map(|score| WinningBoard {
+                        index: i,
+                        last_number_index: index,
+                        score,
+                    })
+                    .into()
+            });
+            Some(winning_boards.collect())
+        } else {
+            None
+        }
+    }
+
+    pub fn draw_number(&mut self) -> bool {
+        if let Some(n) = self.numbers.get(self.last_number_index) {
+            for board in &mut self.boards {
+                board.mark(*n, self.last_number_index);
+            }
+
+            self.last_number_index += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl FromStr for Bingo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+
+        let numbers_line = lines.next().unwrap_or_default();
+        let numbers = numbers_line
+            .split(',')
+            .map(|c| parse_token(Day04::DAY, numbers_line, c, "a drawn number"))
+            .collect::<Result<_, _>>()?;
+
+        let mut boards = vec![];
+        let mut board: Option<Board> = None;
+
+        for (i, line) in lines.enumerate() {
+            if line.is_empty() {
+                match board {
+                    Some(b) if b.rows.is_empty() => {
+                        return Err(
+                            ParseError::new(Day04::DAY, line, line, "a board row").on_line(i + 2)
+                        )
+                    }
+                    Some(b) => boards.push(b),
+                    None => {}
+                }
+                board = Some(Board::new());
+                continue;
+            }
+
+            match board.as_mut() {
+                Some(b) => b.add_row(line).map_err(|e| e.on_line(i + 2))?,
+                None => {
+                    return Err(
+                        ParseError::new(Day04::DAY, line, line, "an empty line").on_line(i + 2)
+                    )
+                }
+            }
+        }
+
+        match board {
+            Some(b) if b.rows.is_empty() => {
+                let end = s.lines().count() + 1;
+                return Err(ParseError::new(Day04::DAY, "", "", "a board row").on_line(end));
+            }
+            Some(b) => boards.push(b),
+            None => {}
+        }
+
+        Ok(Self {
+            numbers,
+            last_number_index: 0,
+            boards,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct WinningBoard {
+    pub index: usize,
+    pub last_number_index: usize,
+    pub score: usize,
+}
+
+impl Display for WinningBoard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.score)
+    }
+}
+
+impl Answer for WinningBoard {
+    fn extras(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("board_index", self.index as i64),
+            ("last_number_index", self.last_number_index as i64),
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BoardNumber {
+    Unmarked(usize),
+    Marked(usize),
+}
+
+impl BoardNumber {
+    pub fn is_marked(&self) -> bool {
+        match self {
+            BoardNumber::Marked(_) => true,
+            BoardNumber::Unmarked(_) => false,
+        }
+    }
+
+    pub fn is_unmarked(&self) -> bool {
+        !self.is_marked()
+    }
+
+    pub fn value(&self) -> usize {
+        match self {
+            BoardNumber::Marked(n) => *n,
+            BoardNumber::Unmarked(n) => *n,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Board {
+    rows: Vec<Vec<BoardNumber>>,
+    winning_number_and_index: Option<(usize, usize)>,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self {
+            rows: vec![],
+            winning_number_and_index: None,
+        }
+    }
+
+    pub fn add_row(&mut self, line: &str) -> Result<(), ParseError> {
+        let row = line
+            .split_whitespace()
+            .map(|c| parse_token(Day04::DAY, line, c, "a board number").map(BoardNumber::Unmarked))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(first) = self.rows.first() {
+            if row.len() != first.len() {
+                let expected = format!("a row of {} numbers", first.len());
+                return Err(ParseError::new(Day04::DAY, line, line, expected));
+            }
+        }
+
+        self.rows.push(row);
+        Ok(())
+    }
+
+    pub fn mark(&mut self, number: usize, index: usize) {
+        if !self.is_winner() {
+            for row in &mut self.rows {
+                for board_number in row {
+                    match board_number {
+                        BoardNumber::Unmarked(n) if *n == number => {
+                            *board_number = BoardNumber::Marked(*n);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let row_win = self.rows.iter().any(|r| r.iter().all(|b| b.is_marked()));
+            let column_win = (0..self.rows[0].len())
+                .any(|i| self.rows.iter().map(|r| &r[i]).all(|b| b.is_marked()));
+            if row_win || column_win {
+                self.winning_number_and_index = Some((number, index));
+            }
+        }
+    }
+
+    pub fn is_winner(&self) -> bool {
+        self.winning_number_and_index.is_some()
+    }
+
+    /// The number the board won with and its index in the drawn numbers, if it has won
+    pub fn winning_number_and_index(&self) -> Option<(usize, usize)> {
+        self.winning_number_and_index
+    }
+
+    /// The score of a board that won with a number, or `None` if it's too large to count. A board
+    /// isn't marked once it has won, so its unmarked numbers are those left when it won.
+    pub fn score(&self, winning_number: usize) -> Option<usize> {
+        self.sum_unmarked_numbers()?.checked_mul(winning_number)
+    }
+
+    fn sum_unmarked_numbers(&self) -> Option<usize> {
+        self.rows
+            .iter()
+            .flat_map(|r| r.iter().filter(|b| b.is_unmarked()).map(|b| b.value()))
+            .try_fold(0_usize, |sum, n| sum.checked_add(n))
+    }
+}
+
+/// The answer to both parts when every number is drawn without any board winning
+const NO_WINNER: &str = "no board wins";
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: usize = 4;
+    type Input = Bingo;
+    type Part1 = Checked<WinningBoard>;
+    type Part2 = Checked<WinningBoard>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Bingo::from_str(input)
+    }
+
+    fn part_1(bingo: &Self::Input) -> Self::Part1 {
+        let mut bingo = bingo.clone();
+        while bingo.winning_boards().is_none() && bingo.draw_number() {}
+
+        match bingo.winning_boards() {
+            Some(mut boards) => boards.remove(0),
+            None => Checked::NoAnswer(NO_WINNER),
+        }
+    }
+
+    fn part_2(bingo: &Self::Input) -> Self::Part2 {
+        let mut bingo = bingo.clone();
+        while bingo.draw_number() {}
+
+        match bingo.winning_boards() {
+            Some(mut boards) => boards.pop().unwrap(),
+            None => Checked::NoAnswer(NO_WINNER),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn part_1_finds_the_first_winning_board() {
+        let bingo = Day04::parse(EXAMPLE).unwrap();
+        let answer = Day04::part_1(&bingo);
+        let board = answer.answer().unwrap();
+        assert_eq!(board.index, 2);
+        // the board wins when 24 is drawn
+        assert_eq!(board.last_number_index, 11);
+        assert_eq!(board.score, 188 * 24);
+        assert_eq!(board.to_string(), "4512");
+    }
+
+    #[test]
+    fn part_2_finds_the_last_winning_board() {
+        let bingo = Day04::parse(EXAMPLE).unwrap();
+        let answer = Day04::part_2(&bingo);
+        let board = answer.answer().unwrap();
+        assert_eq!(board.index, 1);
+        // the board wins when 13 is drawn
+        assert_eq!(board.last_number_index, 14);
+        assert_eq!(board.score, 148 * 13);
+        assert_eq!(board.to_string(), "1924");
+    }
+
+    #[test]
+    fn answers_that_no_board_wins_when_none_do() {
+        let bingo = Day04::parse("1,4\n\n1 2\n3 4").unwrap();
+        assert_eq!(Day04::part_1(&bingo).to_string(), "no board wins");
+        assert_eq!(Day04::part_2(&bingo).to_string(), "no board wins");
+        assert!(Day04::part_1(&bingo).extras().is_empty());
+        assert!(Day04::parse("1,2\n\n\n1 2\n3 4").is_err());
+    }
+
+    #[test]
+    fn answers_overflow_for_scores_too_large_to_count() {
+        let max = usize::MAX;
+        // the board wins with 1 on the bottom row, leaving the largest number unmarked
+        let bingo = Day04::parse(&format!("1\n\n{} 0\n1 1", max)).unwrap();
+        assert_eq!(Day04::part_1(&bingo).answer().unwrap().score, max);
+
+        let bingo = Day04::parse(&format!("2,1\n\n1 2\n3 {}", max)).unwrap();
+        assert_eq!(Day04::part_1(&bingo), Checked::Overflow);
+        assert_eq!(Day04::part_1(&bingo).to_string(), "overflow");
+    }
+}
@@ -0,0 +1,292 @@
+use crate::day05::LineType::{Diagonal, Horizontal, Vertical};
+use crate::day05::Point::{Marked, Unmarked};
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::grid::Grid;
+use crate::image::Rgb;
+use crate::solution::Solution;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum LineType {
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+pub type Coord = (usize, usize);
+
+#[derive(Debug, Clone)]
+pub struct LineSegment {
+    start: Coord,
+    end: Coord,
+    curr: Option<Coord>,
+    line_type: LineType,
+}
+
+impl LineSegment {
+    fn parse_coords(line: &str, s: &str) -> Result<Coord, ParseError> {
+        let (x, y) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(Day05::DAY, line, s, "a coordinate, e.g. '0,9'"))?;
+        Ok((
+            parse_token(Day05::DAY, line, x, "an x coordinate")?,
+            parse_token(Day05::DAY, line, y, "a y coordinate")?,
+        ))
+    }
+
+    pub fn line_type(&self) -> LineType {
+        self.line_type
+    }
+
+    pub fn max_coords(&self) -> Coord {
+        let max_x = std::cmp::max(self.start.0, self.end.0);
+        let max_y = std::cmp::max(self.start.1, self.end.1);
+        (max_x, max_y)
+    }
+}
+
+impl FromStr for LineSegment {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once(" -> ").ok_or_else(|| {
+            ParseError::new(Day05::DAY, s, s, "a line segment, e.g. '0,9 -> 5,9'")
+        })?;
+        let start = Self::parse_coords(s, start)?;
+        let end = Self::parse_coords(s, end)?;
+        let x_diff = start.0 as i64 - end.0 as i64;
+        let y_diff = start.1 as i64 - end.1 as i64;
+        let line_type = match (x_diff, y_diff) {
+            (0, _) => Vertical,
+            (_, 0) => Horizontal,
+            (x, y) if x.abs() == y.abs() => Diagonal,
+            _ => {
+                return Err(ParseError::new(
+                    Day05::DAY,
+                    s,
+                    s,
+                    "a horizontal, vertical or 45 degree diagonal line",
+                ))
+            }
+        };
+
+        Ok(Self {
+            start,
+            end,
+            curr: None,
+            line_type,
+        })
+    }
+}
+
+impl Iterator for LineSegment {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.curr.is_none() {
+            self.curr = Some(self.start);
+            self.curr
+        } else if self.curr == Some(self.end) {
+            None
+        } else {
+            self.curr = self.curr.map(|c| {
+                let x = match c.0.cmp(&self.end.0) {
+                    Ordering::Less => c.0 + 1,
+                    Ordering::Equal => c.0,
+                    Ordering::Greater => c.0 - 1,
+                };
+
+                let y = match c.1.cmp(&self.end.1) {
+                    Ordering::Less => c.1 + 1,
+                    Ordering::Equal => c.1,
+                    Ordering::Greater => c.1 - 1,
+                };
+
+                (x, y)
+            });
+            self.curr
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Point {
+    Unmarked,
+    Marked(usize),
+}
+
+impl std::fmt::Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unmarked => write!(f, "."),
+            Marked(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+pub struct Plot {
+    coords: Vec<Vec<Point>>,
+}
+
+impl Plot {
+    pub fn new(line_segments: &mut [LineSegment]) -> Self {
+        let (max_x, max_y): (Vec<_>, Vec<_>) = line_segments.iter().map(|l| l.max_coords()).unzip();
+
+        let width = max_x.iter().max().map_or(0, |x| x + 1);
+        let height = max_y.iter().max().map_or(0, |y| y + 1);
+        let mut coords = vec![vec![Unmarked; width]; height];
+        for line_segment in line_segments {
+            for (x, y) in line_segment {
+                coords[y][x] = match coords[y][x] {
+                    Unmarked => Marked(1),
+                    Marked(n) => Marked(n + 1),
+                }
+            }
+        }
+
+        Self { coords }
+    }
+
+    /// An image of the plot, coloured by the number of lines at each point
+    pub fn image(&self) -> Grid<Rgb> {
+        let lines = |p: &Point| match p {
+            Unmarked => 0,
+            Marked(n) => *n,
+        };
+        let max = self.coords.iter().flatten().map(lines).max().unwrap_or(0);
+        let (width, height) = (self.coords.first().map_or(0, Vec::len), self.coords.len());
+        Grid::from_fn(width, height, |(x, y)| match self.coords[y][x] {
+            Unmarked => Rgb::BLACK,
+            p => Rgb::scale(lines(&p), max),
+        })
+    }
+
+    pub fn overlaps(&self) -> usize {
+        self.coords
+            .iter()
+            .flat_map(|r| r.iter().filter(|p| matches!(p, Marked(n) if *n > 1)))
+            .count()
+    }
+}
+
+impl std::fmt::Display for Plot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in &self.coords {
+            for (i, point) in row.iter().enumerate() {
+                if i == row.len() - 1 {
+                    writeln!(f, "{}", point).unwrap();
+                } else {
+                    write!(f, "{}", point).unwrap();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The number of points where at least two lines overlap. Only the points on the lines are
+/// counted, so lines far apart don't need a plot of all the area between them.
+pub fn overlaps<'a>(line_segments: impl Iterator<Item = &'a LineSegment>) -> usize {
+    let mut lines: HashMap<Coord, usize> = HashMap::new();
+    for line_segment in line_segments {
+        for coord in line_segment.clone() {
+            *lines.entry(coord).or_default() += 1;
+        }
+    }
+    lines.values().filter(|&&n| n > 1).count()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: usize = 5;
+    type Input = Vec<LineSegment>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, LineSegment::from_str)
+    }
+
+    fn part_1(line_segments: &Self::Input) -> Self::Part1 {
+        overlaps(line_segments.iter().filter(|l| l.line_type() != Diagonal))
+    }
+
+    fn part_2(line_segments: &Self::Input) -> Self::Part2 {
+        overlaps(line_segments.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn line_segments_include_both_ends() {
+        let line_segment = LineSegment::from_str("9,7 -> 7,9").unwrap();
+        assert_eq!(line_segment.line_type(), Diagonal);
+        assert_eq!(line_segment.collect::<Vec<_>>(), [(9, 7), (8, 8), (7, 9)]);
+    }
+
+    #[test]
+    fn part_1_counts_horizontal_and_vertical_overlaps() {
+        let line_segments = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_1(&line_segments), 5);
+    }
+
+    #[test]
+    fn part_2_counts_all_overlaps() {
+        let line_segments = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_2(&line_segments), 12);
+
+        let plot = Plot::new(&mut line_segments.clone());
+        assert_eq!(
+            plot.to_string(),
+            "\
+1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111....
+"
+        );
+    }
+
+    #[test]
+    fn counts_overlaps_of_only_diagonals_and_of_distant_lines() {
+        let line_segments = Day05::parse("0,0 -> 2,2").unwrap();
+        assert_eq!(Day05::part_1(&line_segments), 0);
+
+        let far = usize::MAX - 1;
+        let input = format!(
+            "0,0 -> 0,2\n0,1 -> 0,3\n{},{} -> {},{}",
+            far,
+            far,
+            far,
+            far - 2
+        );
+        let line_segments = Day05::parse(&input).unwrap();
+        assert_eq!(Day05::part_1(&line_segments), 2);
+        assert_eq!(Plot::new(&mut line_segments[..2].to_vec()).overlaps(), 2);
+    }
+}
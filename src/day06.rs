@@ -0,0 +1,170 @@
+use crate::error::{parse_token, ParseError};
+use crate::solution::Solution;
+use std::collections::VecDeque;
+
+/// The number of days simulated for part 1
+pub const PART_1_DAYS: usize = 80;
+/// The number of days simulated for part 2
+pub const PART_2_DAYS: usize = 256;
+
+#[derive(Clone)]
+pub struct Ages {
+    counts: VecDeque<usize>,
+    day: usize,
+}
+
+impl Ages {
+    pub fn from_fishes<I: Iterator<Item = usize>>(fishes: I) -> Self {
+        let mut counts = VecDeque::from(vec![0; 9]);
+        for fish in fishes {
+            counts[fish] += 1;
+        }
+        Self { counts, day: 0 }
+    }
+
+    pub fn advance_days(&mut self, days: usize) {
+        self.checked_advance_days(days)
+            .expect("too many fish to count");
+    }
+
+    pub fn count(&self) -> usize {
+        self.checked_count().expect("too many fish to count")
+    }
+
+    /// The number of fish after each of a number of days, in the order of the days. Once there
+    /// are too many fish to count, the counts are `None`.
+    pub fn counts_after(&self, days: &[usize]) -> Vec<(usize, Option<usize>)> {
+        let mut days = days.to_vec();
+        days.sort_unstable();
+        let mut ages = Some(self.clone());
+        days.into_iter()
+            .map(|day| {
+                if let Some(a) = &mut ages {
+                    if a.checked_advance_days(day.saturating_sub(a.day)).is_none() {
+                        ages = None;
+                    }
+                }
+                (day, ages.as_ref().and_then(Ages::checked_count))
+            })
+            .collect()
+    }
+
+    fn checked_advance_days(&mut self, days: usize) -> Option<()> {
+        for _ in 0..days {
+            let spawned = self.counts.pop_front()?;
+            self.counts[6] = self.counts[6].checked_add(spawned)?;
+            self.counts.push_back(spawned);
+            self.day += 1;
+        }
+        Some(())
+    }
+
+    fn checked_count(&self) -> Option<usize> {
+        self.counts
+            .iter()
+            .try_fold(0usize, |sum, &c| sum.checked_add(c))
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: usize = 6;
+    type Input = Ages;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let fishes = input
+            .split(',')
+            .map(
+                |s| match parse_token(Self::DAY, input, s, "a timer from 0 to 8")? {
+                    t @ 0..=8 => Ok(t),
+                    _ => Err(ParseError::new(Self::DAY, input, s, "a timer from 0 to 8")),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Ages::from_fishes(fishes.into_iter()))
+    }
+
+    fn part_1(ages: &Self::Input) -> Self::Part1 {
+        let mut ages = ages.clone();
+        ages.advance_days(PART_1_DAYS);
+        ages.count()
+    }
+
+    fn part_2(ages: &Self::Input) -> Self::Part2 {
+        let mut ages = ages.clone();
+        ages.advance_days(PART_2_DAYS);
+        ages.count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "3,4,3,1,2";
+
+    #[test]
+    fn counts_fish_after_each_day() {
+        let mut ages = Day06::parse(EXAMPLE).unwrap();
+        ages.advance_days(1);
+        assert_eq!(ages.count(), 5);
+        ages.advance_days(1);
+        assert_eq!(ages.count(), 6);
+        ages.advance_days(16);
+        assert_eq!(ages.count(), 26);
+    }
+
+    #[test]
+    fn part_1_counts_fish_after_80_days() {
+        let ages = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part_1(&ages), 5934);
+    }
+
+    #[test]
+    fn part_2_counts_fish_after_256_days() {
+        let ages = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(Day06::part_2(&ages), 26984457539);
+    }
+
+    #[test]
+    fn counts_fish_at_each_checkpoint() {
+        let ages = Day06::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            ages.counts_after(&[80, 18, 256]),
+            [(18, Some(26)), (80, Some(5934)), (256, Some(26984457539))]
+        );
+        assert_eq!(
+            ages.counts_after(&[489, 490]),
+            [(489, Some(17614907331943978900)), (490, None)]
+        );
+    }
+
+    /// Simulates every fish, as the puzzle describes
+    fn simulate(mut timers: Vec<usize>, days: usize) -> usize {
+        for _ in 0..days {
+            let spawned = timers.iter().filter(|&&t| t == 0).count();
+            for timer in &mut timers {
+                *timer = if *timer == 0 { 6 } else { *timer - 1 };
+            }
+            timers.resize(timers.len() + spawned, 8);
+        }
+        timers.len()
+    }
+
+    proptest! {
+        #[test]
+        fn counting_ages_matches_simulating_each_fish(
+            timers in prop::collection::vec(0..=8usize, 0..10),
+            days in 0..60usize,
+        ) {
+            let mut ages = Ages::from_fishes(timers.iter().copied());
+            ages.advance_days(days);
+            prop_assert_eq!(ages.count(), simulate(timers, days));
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::error::{parse_token, ParseError};
+use crate::solution::{Answer, Checked, Solution};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+pub struct Positions {
+    values: Vec<i32>,
+    sorted: bool,
+}
+
+pub struct Outcome {
+    pub position: i32,
+    pub fuel: i64,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.fuel)
+    }
+}
+
+impl Answer for Outcome {
+    fn extras(&self) -> Vec<(&'static str, i64)> {
+        vec![("position", self.position as i64)]
+    }
+}
+
+impl Positions {
+    pub fn new(values: Vec<i32>) -> Self {
+        Self {
+            values,
+            sorted: false,
+        }
+    }
+
+    fn median(&mut self) -> i32 {
+        if !self.sorted {
+            self.values.sort_unstable();
+            self.sorted = true;
+        }
+
+        let mid = self.values.len() / 2;
+        if self.values.len().is_multiple_of(2) {
+            Self::calc_mean(&self.values[(mid - 1)..=mid]) as i32
+        } else {
+            self.values[mid]
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        Self::calc_mean(&self.values)
+    }
+
+    fn calc_mean(positions: &[i32]) -> f64 {
+        let sum: i64 = positions.iter().map(|&p| p as i64).sum();
+        sum as f64 / positions.len() as f64
+    }
+
+    /// The sum of 1 to `number`. Crabs are at most 2³² - 1 apart, and the sum up to that is
+    /// 2⁶³ - 2³¹, which fits in an `i64` when the even one of `number` and `number + 1` is halved
+    /// before multiplying.
+    fn seq_sum(number: i64) -> i64 {
+        if number % 2 == 0 {
+            number / 2 * (number + 1)
+        } else {
+            (number + 1) / 2 * number
+        }
+    }
+
+    fn distance(a: i32, b: i32) -> i64 {
+        (a as i64 - b as i64).abs()
+    }
+
+    /// Part 1's fuel can't overflow, as it would take 2³¹ crabs each 2³² - 1 from the median
+    pub fn part_1(&mut self) -> Outcome {
+        let position = self.median();
+        let fuel = self
+            .values
+            .iter()
+            .map(|&p| Self::distance(p, position))
+            .sum();
+        Outcome { position, fuel }
+    }
+
+    /// Part 2's fuel can overflow with only a few crabs far apart, so it's counted with checked
+    /// arithmetic
+    pub fn part_2(&mut self) -> Checked<Outcome> {
+        // the best position is within 0.5 of the mean, so is one of the integers either side of it
+        let mean = self.mean().floor() as i32;
+        [mean, mean.saturating_add(1)]
+            .iter()
+            .filter_map(|&position| {
+                let fuel = self.values.iter().try_fold(0_i64, |fuel, &value| {
+                    fuel.checked_add(Self::seq_sum(Self::distance(position, value)))
+                })?;
+                Some(Outcome { position, fuel })
+            })
+            .min_by_key(|o| o.fuel)
+            .into()
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: usize = 7;
+    type Input = Positions;
+    type Part1 = Outcome;
+    type Part2 = Checked<Outcome>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let values = input
+            .split(',')
+            .map(|s| parse_token(Self::DAY, input, s, "a position"))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Positions::new(values))
+    }
+
+    fn part_1(positions: &Self::Input) -> Self::Part1 {
+        positions.clone().part_1()
+    }
+
+    fn part_2(positions: &Self::Input) -> Self::Part2 {
+        positions.clone().part_2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn part_1_aligns_on_the_median() {
+        let positions = Day07::parse(EXAMPLE).unwrap();
+        let outcome = Day07::part_1(&positions);
+        assert_eq!(outcome.position, 2);
+        assert_eq!(outcome.fuel, 37);
+    }
+
+    #[test]
+    fn part_2_aligns_next_to_the_mean() {
+        let positions = Day07::parse(EXAMPLE).unwrap();
+        let outcome = Day07::part_2(&positions);
+        let outcome = outcome.answer().unwrap();
+        assert_eq!(outcome.position, 5);
+        assert_eq!(outcome.fuel, 168);
+    }
+
+    #[test]
+    fn counts_fuel_for_the_furthest_positions() {
+        assert_eq!(
+            Positions::seq_sum(u32::MAX as i64),
+            i64::MAX - (1 << 31) + 1
+        );
+
+        let positions = Day07::parse("-2147483648,2147483647").unwrap();
+        assert_eq!(Day07::part_1(&positions).fuel, u32::MAX as i64);
+        // both crabs move about 2³¹ to meet in the middle
+        let outcome = Day07::part_2(&positions);
+        assert_eq!(outcome.answer().unwrap().fuel, 1 << 62);
+
+        let positions = Day07::parse("-2147483648,-2147483648,2147483647,2147483647").unwrap();
+        assert_eq!(Day07::part_1(&positions).fuel, 2 * u32::MAX as i64);
+        assert_eq!(Day07::part_2(&positions).to_string(), "overflow");
+    }
+
+    /// The least fuel to align on any position between the crabs, trying each in turn
+    fn least_fuel(values: &[i32], cost: impl Fn(i64) -> i64) -> i64 {
+        let (min, max) = (*values.iter().min().unwrap(), *values.iter().max().unwrap());
+        (min..=max)
+            .map(|p| values.iter().map(|v| cost((p - v).abs() as i64)).sum())
+            .min()
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn median_and_mean_find_the_least_fuel(
+            values in prop::collection::vec(0..100i32, 1..20),
+        ) {
+            let mut positions = Positions::new(values.clone());
+            prop_assert_eq!(positions.part_1().fuel, least_fuel(&values, |d| d));
+            prop_assert_eq!(
+                positions.part_2().answer().unwrap().fuel,
+                least_fuel(&values, Positions::seq_sum)
+            );
+        }
+    }
+}
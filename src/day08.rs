@@ -0,0 +1,363 @@
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+/// The number of segments each digit shares with the digits 1 and 4
+const SHARED_WITH_1_AND_4: [(usize, usize); 10] = [
+    (2, 3),
+    (2, 2),
+    (1, 2),
+    (2, 3),
+    (2, 4),
+    (1, 3),
+    (1, 3),
+    (2, 2),
+    (2, 4),
+    (2, 4),
+];
+
+pub struct SignalPatterns {
+    outputs: Vec<usize>,
+    value: usize,
+}
+
+impl SignalPatterns {
+    fn validate<'a>(
+        line: &'a str,
+        s: &'a str,
+        count: usize,
+        expected: &str,
+    ) -> Result<Vec<&'a str>, ParseError> {
+        let patterns: Vec<_> = s.split(' ').collect();
+        if patterns.len() != count {
+            return Err(ParseError::new(Day08::DAY, line, s, expected));
+        }
+
+        for pattern in &patterns {
+            if pattern.is_empty() || pattern.len() > 7 {
+                return Err(ParseError::new(
+                    Day08::DAY,
+                    line,
+                    pattern,
+                    "1 to 7 segments",
+                ));
+            }
+
+            for (i, c) in pattern.char_indices() {
+                if !('a'..='g').contains(&c) || pattern[..i].contains(c) {
+                    let token = &pattern[i..i + c.len_utf8()];
+                    return Err(ParseError::new(
+                        Day08::DAY,
+                        line,
+                        token,
+                        "a distinct segment from 'a' to 'g'",
+                    ));
+                }
+            }
+        }
+
+        Ok(patterns)
+    }
+}
+
+impl FromStr for SignalPatterns {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern_list, output_list) = s.split_once(" | ").ok_or_else(|| {
+            ParseError::new(
+                Day08::DAY,
+                s,
+                s,
+                "signal patterns and outputs separated by ' | '",
+            )
+        })?;
+        let patterns = Self::validate(s, pattern_list, 10, "10 signal patterns")?;
+        let outputs = Self::validate(s, output_list, 4, "4 output values")?;
+        let invalid =
+            || ParseError::new(Day08::DAY, s, pattern_list, "the patterns of digits 0 to 9");
+
+        let mut inputs = {
+            let mut digits: Vec<Digit> = patterns.into_iter().map(Digit::from).collect();
+            digits.sort_by_key(|d| d.len);
+            // the digits have 2, 3, 4, 5, 5, 5, 6, 6, 6 and 7 segments, which the deduction relies on
+            let lens: Vec<_> = digits.iter().map(|d| d.len).collect();
+            if lens != [2, 3, 4, 5, 5, 5, 6, 6, 6, 7] {
+                return Err(invalid());
+            }
+            VecDeque::from_iter(digits.into_iter().map(|i| i.hash))
+        };
+
+        let outputs: Vec<Vec<char>> = outputs
+            .into_iter()
+            .map(|dd| {
+                let mut ch: Vec<_> = dd.chars().collect();
+                ch.sort_unstable();
+                ch
+            })
+            .collect();
+
+        let mut digits = vec![None; 10];
+        digits[1] = inputs.pop_front();
+        digits[7] = inputs.pop_front();
+        digits[4] = inputs.pop_front();
+        digits[8] = inputs.pop_back();
+
+        // calculate 3
+        let one = digits[1].clone().unwrap();
+        let i = (0..3)
+            .find(|&i| inputs[i].difference(&one).count() == 3)
+            .ok_or_else(invalid)?;
+        digits[3] = inputs.remove(i);
+
+        // [2|5, 5|2, 0|6|9, 6|9|0, 9|0|6];
+        // calculate 9
+        let three = digits[3].clone().unwrap();
+        let i = (2..5)
+            .find(|&i| inputs[i].difference(&three).count() == 1)
+            .ok_or_else(invalid)?;
+        digits[9] = inputs.remove(i);
+
+        // calculate 2 and 5
+        if inputs[0].difference(digits[9].as_ref().unwrap()).count() == 0 {
+            digits[5] = inputs.pop_front();
+            digits[2] = inputs.pop_front();
+        } else {
+            digits[2] = inputs.pop_front();
+            digits[5] = inputs.pop_front();
+        }
+
+        // calculate 0 and 6
+        if inputs[0].difference(digits[5].as_ref().unwrap()).count() == 1 {
+            digits[6] = inputs.pop_front();
+            digits[0] = inputs.pop_front();
+        } else {
+            digits[0] = inputs.pop_front();
+            digits[6] = inputs.pop_front();
+        }
+
+        let digits: Vec<HashSet<char>> = digits.into_iter().map(Option::unwrap).collect();
+
+        // patterns that aren't the ten digits can still be deduced as something, so check that
+        // each shares as many segments with 1 and 4 as it should
+        let shared = |d: &HashSet<char>, i: usize| d.intersection(&digits[i]).count();
+        if digits
+            .iter()
+            .zip(SHARED_WITH_1_AND_4)
+            .any(|(d, expected)| (shared(d, 1), shared(d, 4)) != expected)
+        {
+            return Err(invalid());
+        }
+
+        let map: HashMap<_, _> = digits
+            .into_iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let mut v: Vec<char> = h.into_iter().collect();
+                v.sort_unstable();
+                (v, i)
+            })
+            .collect();
+        if map.len() != 10 {
+            return Err(invalid());
+        }
+
+        let outputs: Vec<_> = outputs
+            .iter()
+            .map(|d| {
+                map.get(d).copied().ok_or_else(|| {
+                    ParseError::new(
+                        Day08::DAY,
+                        s,
+                        output_list,
+                        "outputs that match the patterns",
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let value = outputs
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, v)| v * 10usize.pow(i as u32))
+            .sum();
+
+        Ok(SignalPatterns { outputs, value })
+    }
+}
+
+impl SignalPatterns {
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    pub fn value(&self) -> usize {
+        self.value
+    }
+}
+
+pub struct Digit {
+    len: usize,
+    hash: HashSet<char>,
+}
+
+impl Digit {
+    pub fn from(s: &str) -> Self {
+        let hash: HashSet<char> = s.chars().collect();
+        let len = s.len();
+        Self { len, hash }
+    }
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: usize = 8;
+    type Input = Vec<SignalPatterns>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, SignalPatterns::from_str)
+    }
+
+    fn part_1(signal_patterns: &Self::Input) -> Self::Part1 {
+        signal_patterns
+            .iter()
+            .map(|p| {
+                p.outputs()
+                    .iter()
+                    .filter(|v| matches!(v, 1 | 4 | 7 | 8))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn part_2(signal_patterns: &Self::Input) -> Self::Part2 {
+        signal_patterns.iter().map(|p| p.value()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn deduces_the_digits_of_a_single_entry() {
+        let patterns = SignalPatterns::from_str(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        )
+        .unwrap();
+        assert_eq!(patterns.outputs(), [5, 3, 5, 3]);
+        assert_eq!(patterns.value(), 5353);
+    }
+
+    #[test]
+    fn part_1_counts_digits_with_unique_segment_counts() {
+        let patterns = Day08::parse(EXAMPLE).unwrap();
+        assert_eq!(Day08::part_1(&patterns), 26);
+    }
+
+    #[test]
+    fn part_2_sums_output_values() {
+        let patterns = Day08::parse(EXAMPLE).unwrap();
+        let values: Vec<_> = patterns.iter().map(|p| p.value()).collect();
+        assert_eq!(
+            values,
+            [8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315]
+        );
+        assert_eq!(Day08::part_2(&patterns), 61229);
+    }
+
+    #[test]
+    fn rejects_patterns_that_are_not_the_ten_digits() {
+        for line in [
+            "ab ab abc abcd abcde abcdf abcdg abcdef abcdeg abcdefg | ab ab ab ab",
+            "ab abc abcd abcde abcdf abcdg abcdef abcdeg abcdfg abcdefg | ab ab ab ab",
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbag",
+        ] {
+            assert!(SignalPatterns::from_str(line).is_err(), "{}", line);
+        }
+    }
+
+    /// The segments of each digit when the wires aren't mixed up
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+
+    fn rewire(segments: &str, wires: &[char]) -> String {
+        segments
+            .chars()
+            .map(|s| wires[(s as u8 - b'a') as usize])
+            .collect()
+    }
+
+    /// The segments of a pattern as bits, so that the order they're listed in doesn't matter
+    fn mask(pattern: &str) -> u8 {
+        pattern.bytes().fold(0, |mask, s| mask | 1 << (s - b'a'))
+    }
+
+    /// Decodes the outputs by trying every wiring until one turns all ten patterns into digits
+    fn decode(patterns: &[String], outputs: &[String]) -> Vec<usize> {
+        let mut wires: Vec<char> = "abcdefg".chars().collect();
+        let mut wirings = vec![];
+        permutations(&mut wires, 0, &mut wirings);
+
+        let patterns: HashSet<u8> = patterns.iter().map(|p| mask(p)).collect();
+        let digits = wirings
+            .into_iter()
+            .map(|wires| DIGITS.map(|d| mask(&rewire(d, &wires))))
+            .find(|digits| digits.iter().all(|d| patterns.contains(d)))
+            .unwrap();
+        outputs
+            .iter()
+            .map(|o| digits.iter().position(|&d| d == mask(o)).unwrap())
+            .collect()
+    }
+
+    fn permutations(wires: &mut Vec<char>, from: usize, all: &mut Vec<Vec<char>>) {
+        if from == wires.len() {
+            all.push(wires.clone());
+            return;
+        }
+        for i in from..wires.len() {
+            wires.swap(from, i);
+            permutations(wires, from + 1, all);
+            wires.swap(from, i);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn deduction_matches_trying_every_wiring(
+            wires in Just("abcdefg".chars().collect::<Vec<_>>()).prop_shuffle(),
+            order in Just((0..10).collect::<Vec<usize>>()).prop_shuffle(),
+            output in prop::collection::vec(0..10usize, 4),
+        ) {
+            let patterns: Vec<_> = order.iter().map(|&d| rewire(DIGITS[d], &wires)).collect();
+            let outputs: Vec<_> = output.iter().map(|&d| rewire(DIGITS[d], &wires)).collect();
+            let line = format!("{} | {}", patterns.join(" "), outputs.join(" "));
+
+            let signal_patterns = SignalPatterns::from_str(&line).unwrap();
+            prop_assert_eq!(signal_patterns.outputs(), &decode(&patterns, &outputs)[..]);
+        }
+    }
+}
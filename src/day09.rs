@@ -0,0 +1,264 @@
+use crate::animation::{Animation, Control};
+use crate::error::ParseError;
+use crate::grid::{Coord, Grid};
+use crate::image::{Overlay, Rgb};
+use crate::solution::{Checked, Solution};
+use ansi_term::Colour;
+use std::collections::HashSet;
+use std::io;
+use std::str::FromStr;
+
+pub struct Map {
+    points: Grid<Point>,
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let heights = Grid::parse(Day09::DAY, s, "a height from 0 to 9", |c| {
+            c.to_digit(10).map(|d| d as u8)
+        })?;
+        let points = Grid::from_fn(heights.width(), heights.height(), |c| {
+            Point::new(heights[c], c)
+        });
+
+        Ok(Self { points })
+    }
+}
+
+impl Map {
+    pub fn low_points(&self) -> Vec<&Point> {
+        self.points
+            .iter()
+            .map(|(_, p)| p)
+            .filter(|p| p.is_low_point(self))
+            .collect()
+    }
+
+    pub fn points(&self) -> &Grid<Point> {
+        &self.points
+    }
+
+    /// An image of the heights, optionally with each basin in its own colour and the low points
+    /// in red
+    pub fn image(&self, overlays: &[Overlay]) -> Grid<Rgb> {
+        let mut image = self.points.map(|p| Rgb::scale(p.height as usize, 9));
+        let low_points = self.low_points();
+        if overlays.contains(&Overlay::Basins) {
+            for (i, low_point) in low_points.iter().enumerate() {
+                let colour = Rgb::palette(i);
+                for p in low_point.basin(self) {
+                    // higher points are darker, so the shape of the basin still shows
+                    image[p.coord] = colour.mix(Rgb::BLACK, p.height as f64 / 12.0);
+                }
+            }
+        }
+
+        if overlays.contains(&Overlay::LowPoints) {
+            for p in &low_points {
+                image[p.coord] = Rgb::RED;
+            }
+        }
+
+        image
+    }
+
+    /// Animates every basin filling outwards from its low point, a step at a time. Low points
+    /// are shown in red, and the rest of each basin in blue as it fills.
+    pub fn animate(&self, animation: &mut Animation) -> io::Result<()> {
+        let low_points = self.low_points();
+        let mut filled = self.points.map(|_| false);
+        let mut frontier: Vec<_> = low_points.iter().map(|p| p.coord).collect();
+        for coord in &frontier {
+            filled[*coord] = true;
+        }
+
+        for step in 0.. {
+            let caption = format!(
+                "step {}: {} basins, {} points filled",
+                step,
+                low_points.len(),
+                filled.iter().filter(|(_, f)| **f).count()
+            );
+            let frame = self.render(&filled, &low_points);
+
+            let mut next = vec![];
+            for coord in frontier {
+                for a in self.adjacent_points(&self.points[coord]) {
+                    if a.height != 9 && !filled[a.coord] {
+                        filled[a.coord] = true;
+                        next.push(a.coord);
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                return animation.finish(&caption, &frame);
+            }
+            if animation.show(&caption, &frame)? == Control::Quit {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(())
+    }
+
+    fn render(&self, filled: &Grid<bool>, low_points: &[&Point]) -> Grid<String> {
+        self.points.map(|p| {
+            let height = p.height.to_string();
+            if low_points.contains(&p) {
+                Colour::Red.bold().paint(height).to_string()
+            } else if filled[p.coord] {
+                Colour::Blue.paint(height).to_string()
+            } else {
+                height
+            }
+        })
+    }
+
+    fn adjacent_points<'a>(&'a self, point: &Point) -> impl Iterator<Item = &'a Point> {
+        self.points
+            .neighbours(point.coord)
+            .map(move |c| &self.points[c])
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Point {
+    height: u8,
+    coord: Coord,
+}
+
+impl Point {
+    pub fn new(height: u8, coord: Coord) -> Self {
+        Self { height, coord }
+    }
+
+    pub fn is_low_point(&self, map: &Map) -> bool {
+        map.adjacent_points(self).all(|a| self.height < a.height)
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    pub fn coord(&self) -> Coord {
+        self.coord
+    }
+
+    pub fn risk_level(&self) -> usize {
+        self.height as usize + 1
+    }
+
+    pub fn basin_size(&self, map: &Map) -> usize {
+        self.basin(map).len()
+    }
+
+    /// The points that flow down to this point, bounded by points of height 9
+    pub fn basin<'a>(&'a self, map: &'a Map) -> HashSet<&'a Point> {
+        let mut points = HashSet::new();
+        points.insert(self);
+        // a worklist rather than recursion, as a basin can cover most of a large map
+        let mut unexpanded = vec![self];
+        while let Some(point) = unexpanded.pop() {
+            for a in map.adjacent_points(point) {
+                if a.height != 9 && points.insert(a) {
+                    unexpanded.push(a);
+                }
+            }
+        }
+        points
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: usize = 9;
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = Checked<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::from_str(input)
+    }
+
+    fn part_1(map: &Self::Input) -> Self::Part1 {
+        map.low_points().iter().map(|p| p.risk_level()).sum()
+    }
+
+    fn part_2(map: &Self::Input) -> Self::Part2 {
+        let mut basin_sizes = map
+            .low_points()
+            .iter()
+            .map(|p| p.basin_size(map))
+            .collect::<Vec<_>>();
+
+        // each basin has a low point, so there are only three basins to multiply with three
+        if basin_sizes.len() < 3 {
+            return Checked::NoAnswer("fewer than 3 basins");
+        }
+        basin_sizes.sort_by(|a, b| b.cmp(a));
+        basin_sizes[0..3]
+            .iter()
+            .try_fold(1_usize, |product, &size| product.checked_mul(size))
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn part_1_sums_low_point_risk_levels() {
+        let map = Day09::parse(EXAMPLE).unwrap();
+        let low_points: Vec<_> = map.low_points().iter().map(|p| p.coord()).collect();
+        assert_eq!(low_points, [(1, 0), (9, 0), (2, 2), (6, 4)]);
+        assert_eq!(Day09::part_1(&map), 15);
+    }
+
+    #[test]
+    fn part_2_multiplies_the_three_largest_basins() {
+        let map = Day09::parse(EXAMPLE).unwrap();
+        let basin_sizes: Vec<_> = map
+            .low_points()
+            .iter()
+            .map(|p| p.basin_size(&map))
+            .collect();
+        assert_eq!(basin_sizes, [3, 9, 14, 9]);
+        assert_eq!(Day09::part_2(&map), Checked::Answer(1134));
+    }
+
+    #[test]
+    fn fills_a_basin_covering_a_large_map() {
+        // with no 9s to wall them off, the three low points share one basin of every location
+        let mut rows = vec!["5".repeat(600); 600];
+        rows[0].replace_range(0..1, "0");
+        rows[300].replace_range(300..301, "0");
+        rows[599].replace_range(599..600, "0");
+        let map = Day09::parse(&rows.join("\n")).unwrap();
+        assert_eq!(map.low_points().len(), 3);
+        assert_eq!(
+            Day09::part_2(&map),
+            Checked::Answer((600 * 600_usize).pow(3))
+        );
+    }
+
+    #[test]
+    fn answers_that_there_are_too_few_basins_to_multiply() {
+        let map = Day09::parse("191\n999\n999").unwrap();
+        assert_eq!(Day09::part_2(&map).to_string(), "fewer than 3 basins");
+        let map = Day09::parse("191\n999\n191").unwrap();
+        assert_eq!(Day09::part_2(&map), Checked::Answer(1));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::{parse_lines, ParseError};
+use crate::solution::{Checked, Solution};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct IllegalOutput {
+    actual: char,
+}
+
+impl IllegalOutput {
+    pub fn error_score(&self) -> usize {
+        match self.actual {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            '>' => 25137,
+            _ => unreachable!(),
+        }
+    }
+}
+
+static PAIR: Lazy<HashMap<char, char>> = Lazy::new(|| {
+    let mut h = HashMap::new();
+    h.insert('(', ')');
+    h.insert('[', ']');
+    h.insert('{', '}');
+    h.insert('<', '>');
+    h
+});
+
+#[derive(Debug)]
+pub struct SyntaxChecker {
+    line: String,
+}
+
+impl SyntaxChecker {
+    pub fn is_illegal(&self) -> Option<IllegalOutput> {
+        let mut opens = Vec::with_capacity(self.line.len());
+        for actual in self.line.chars() {
+            match actual {
+                '(' | '[' | '{' | '<' => opens.push(actual),
+                ')' | ']' | '}' | '>' => {
+                    if let Some(o) = opens.pop() {
+                        let expected = PAIR[&o];
+                        if expected != actual {
+                            return Some(IllegalOutput { actual });
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        None
+    }
+
+    /// The completion score, or `None` if it's too large to count
+    pub fn completion_score(&self) -> Option<usize> {
+        let mut opens = Vec::with_capacity(self.line.len());
+        for actual in self.line.chars() {
+            match actual {
+                '(' | '[' | '{' | '<' => opens.push(PAIR[&actual]),
+                ')' | ']' | '}' | '>' => {
+                    let _ = opens.pop();
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        opens.into_iter().rev().try_fold(0usize, |acc, c| {
+            let point = match c {
+                ')' => 1,
+                ']' => 2,
+                '}' => 3,
+                '>' => 4,
+                _ => unreachable!(),
+            };
+            acc.checked_mul(5)?.checked_add(point)
+        })
+    }
+}
+
+impl FromStr for SyntaxChecker {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if let Some(i) = line.find(|c| !"()[]{}<>".contains(c)) {
+            let token = &line[i..i + line[i..].chars().next().map_or(1, char::len_utf8)];
+            return Err(ParseError::new(Day10::DAY, line, token, "a bracket"));
+        }
+
+        Ok(Self {
+            line: line.to_string(),
+        })
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+    type Input = Vec<SyntaxChecker>;
+    type Part1 = usize;
+    type Part2 = Checked<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, SyntaxChecker::from_str)
+    }
+
+    fn part_1(checkers: &Self::Input) -> Self::Part1 {
+        checkers
+            .iter()
+            .filter_map(|c| c.is_illegal())
+            .map(|o| o.error_score())
+            .sum()
+    }
+
+    fn part_2(checkers: &Self::Input) -> Self::Part2 {
+        let mut completion_scores = match checkers
+            .iter()
+            .filter(|c| c.is_illegal().is_none())
+            .map(|c| c.completion_score())
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(scores) => scores,
+            None => return Checked::Overflow,
+        };
+        if completion_scores.is_empty() {
+            return Checked::NoAnswer("every line is corrupted");
+        }
+
+        completion_scores.sort_unstable();
+        let middle = completion_scores.len() / 2;
+        Checked::Answer(completion_scores[middle])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn part_1_scores_the_first_illegal_character() {
+        let checkers = Day10::parse(EXAMPLE).unwrap();
+        let illegal: Vec<_> = checkers
+            .iter()
+            .filter_map(|c| c.is_illegal())
+            .map(|o| o.actual)
+            .collect();
+        assert_eq!(illegal, ['}', ')', ']', ')', '>']);
+        assert_eq!(Day10::part_1(&checkers), 26397);
+    }
+
+    #[test]
+    fn part_2_takes_the_middle_completion_score() {
+        let checkers = Day10::parse(EXAMPLE).unwrap();
+        let scores: Vec<_> = checkers
+            .iter()
+            .filter(|c| c.is_illegal().is_none())
+            .map(|c| c.completion_score().unwrap())
+            .collect();
+        assert_eq!(scores, [288957, 5566, 1480781, 995444, 294]);
+        assert_eq!(Day10::part_2(&checkers), Checked::Answer(288957));
+    }
+
+    #[test]
+    fn answers_that_every_line_is_corrupted_when_none_can_be_completed() {
+        let checkers = Day10::parse("(]\n<)").unwrap();
+        assert_eq!(Day10::part_1(&checkers), 57 + 3);
+        assert_eq!(
+            Day10::part_2(&checkers).to_string(),
+            "every line is corrupted"
+        );
+        let checkers = Day10::parse("").unwrap();
+        assert_eq!(
+            Day10::part_2(&checkers),
+            Checked::NoAnswer("every line is corrupted")
+        );
+    }
+
+    #[test]
+    fn answers_overflow_for_completion_scores_too_large_to_count() {
+        let checkers = Day10::parse(&format!("[]\n{}", "(".repeat(40))).unwrap();
+        assert_eq!(Day10::part_2(&checkers), Checked::Overflow);
+        let checkers = Day10::parse(&format!("[]\n{}", "(".repeat(20))).unwrap();
+        assert!(Day10::part_2(&checkers).answer().is_some());
+    }
+}
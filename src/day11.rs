@@ -0,0 +1,303 @@
+use crate::animation::{Animation, Control};
+use crate::error::ParseError;
+use crate::grid;
+use crate::solution::{Answer, Solution};
+use ansi_term::Style;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
+/// The number of steps whose flashes are counted for part 1
+pub const PART_1_STEPS: usize = 100;
+
+#[derive(Debug, Clone)]
+struct Octopus {
+    energy_level: u32,
+    flashed: bool,
+}
+
+impl From<u8> for Octopus {
+    fn from(energy_level: u8) -> Self {
+        Self {
+            energy_level: energy_level as u32,
+            flashed: false,
+        }
+    }
+}
+
+impl Octopus {
+    pub fn increment_and_should_flash(&mut self) -> bool {
+        self.energy_level += 1;
+        self.should_flash()
+    }
+
+    pub fn should_flash(&self) -> bool {
+        self.energy_level > 9 && !self.flashed
+    }
+
+    pub fn flash(&mut self) {
+        self.flashed = true;
+    }
+
+    pub fn reset(&mut self) {
+        if self.flashed {
+            self.energy_level = 0;
+            self.flashed = false;
+        }
+    }
+}
+
+impl Display for Octopus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.energy_level {
+            0 => write!(f, "{}", Style::new().bold().paint("0")),
+            n => write!(f, "{}", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    octopus: grid::Grid<Octopus>,
+    total_flashes: usize,
+    step_flash: usize,
+}
+
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let octopus = grid::Grid::parse_digits(Day11::DAY, s)?.map(|&e| Octopus::from(e));
+        Ok(Self {
+            octopus,
+            total_flashes: 0,
+            step_flash: 0,
+        })
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.octopus)
+    }
+}
+
+impl Grid {
+    pub fn octopus_count(&self) -> usize {
+        self.octopus.len()
+    }
+
+    pub fn advance_step(&mut self) -> usize {
+        let mut flash = self.increment();
+        while flash {
+            flash = self.increment_adjacent();
+        }
+        let step_flash = self.step_flash;
+        self.total_flashes += step_flash;
+        self.reset();
+        step_flash
+    }
+
+    /// Animates each step until all the octopuses flash at once, with flashes shown in bold
+    pub fn animate(&self, animation: &mut Animation) -> io::Result<()> {
+        let mut grid = self.clone();
+        if animation.show("step 0", &grid)? == Control::Quit {
+            return Ok(());
+        }
+
+        for step in 1.. {
+            let flashes = grid.advance_step();
+            let caption = format!(
+                "step {}: {} flashes, {} in total",
+                step,
+                flashes,
+                grid.total_flashes()
+            );
+
+            if flashes == grid.octopus_count() {
+                return animation.finish(&caption, &grid);
+            }
+            if animation.show(&caption, &grid)? == Control::Quit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.step_flash = 0;
+        for octopus in self.octopus.values_mut() {
+            octopus.reset();
+        }
+    }
+
+    fn increment(&mut self) -> bool {
+        let mut flash = false;
+        for octopus in self.octopus.values_mut() {
+            flash |= octopus.increment_and_should_flash();
+        }
+        flash
+    }
+
+    fn increment_adjacent(&mut self) -> bool {
+        let mut flash = false;
+        for coord in self.octopus.coords() {
+            let octopus = &mut self.octopus[coord];
+            if octopus.should_flash() {
+                self.step_flash += 1;
+                octopus.flash();
+
+                for adjacent in self.octopus.neighbours_with_diagonals(coord) {
+                    flash |= self.octopus[adjacent].increment_and_should_flash();
+                }
+            }
+        }
+        flash
+    }
+
+    /// The total number of flashes after each of a number of steps, in the order of the steps
+    pub fn flashes_after(&self, steps: &[usize]) -> Vec<(usize, usize)> {
+        let mut steps = steps.to_vec();
+        steps.sort_unstable();
+        let mut grid = self.clone();
+        let mut step = 0;
+        steps
+            .into_iter()
+            .map(|s| {
+                while step < s {
+                    grid.advance_step();
+                    step += 1;
+                }
+                (s, grid.total_flashes())
+            })
+            .collect()
+    }
+
+    pub fn total_flashes(&self) -> usize {
+        self.total_flashes
+    }
+
+    fn energy_levels(&self) -> Vec<u32> {
+        self.octopus.iter().map(|(_, o)| o.energy_level).collect()
+    }
+}
+
+/// The first step during which every octopus flashes, if they ever all flash at once
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Synchronized {
+    Step(usize),
+    Never,
+}
+
+impl Display for Synchronized {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Synchronized::Step(step) => write!(f, "{}", step),
+            Synchronized::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl Answer for Synchronized {}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = Synchronized;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::from_str(input)
+    }
+
+    fn part_1(grid: &Self::Input) -> Self::Part1 {
+        let mut grid = grid.clone();
+        for _ in 0..PART_1_STEPS {
+            grid.advance_step();
+        }
+
+        grid.total_flashes()
+    }
+
+    fn part_2(grid: &Self::Input) -> Self::Part2 {
+        let mut grid = grid.clone();
+        let mut seen = HashSet::new();
+        let mut step = 1;
+        while grid.advance_step() != grid.octopus_count() {
+            // each step follows from the energy levels, so once they repeat they repeat forever
+            if !seen.insert(grid.energy_levels()) {
+                return Synchronized::Never;
+            }
+            step += 1;
+        }
+
+        Synchronized::Step(step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn flashes_spread_to_adjacent_octopuses() {
+        let mut grid = Grid::from_str("11111\n19991\n19191\n19991\n11111").unwrap();
+        assert_eq!(grid.advance_step(), 9);
+        assert_eq!(grid.advance_step(), 0);
+        assert_eq!(grid.total_flashes(), 9);
+    }
+
+    #[test]
+    fn counts_flashes_in_each_step() {
+        let mut grid = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.advance_step(), 0);
+        assert_eq!(grid.advance_step(), 35);
+        for _ in 2..10 {
+            grid.advance_step();
+        }
+        assert_eq!(grid.total_flashes(), 204);
+    }
+
+    #[test]
+    fn counts_flashes_at_each_checkpoint() {
+        let grid = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            grid.flashes_after(&[100, 10, 10]),
+            [(10, 204), (10, 204), (100, 1656)]
+        );
+    }
+
+    #[test]
+    fn part_1_counts_flashes_after_100_steps() {
+        let grid = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_1(&grid), 1656);
+    }
+
+    #[test]
+    fn part_2_finds_the_first_step_where_all_flash() {
+        let grid = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_2(&grid), Synchronized::Step(195));
+    }
+
+    #[test]
+    fn part_2_stops_when_the_octopuses_never_all_flash() {
+        let grid = Day11::parse("05").unwrap();
+        assert_eq!(Day11::part_2(&grid), Synchronized::Never);
+    }
+}
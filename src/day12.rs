@@ -0,0 +1,221 @@
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+pub type Edge<'a> = (&'a str, &'a str);
+
+#[derive(Debug, Default)]
+pub struct Graph {
+    caves: HashMap<String, Vec<String>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self {
+            caves: HashMap::new(),
+        }
+    }
+
+    pub fn add_edge(&mut self, edge: Edge) {
+        self.add(edge.0, edge.1);
+        self.add(edge.1, edge.0);
+    }
+
+    fn add(&mut self, src: &str, dest: &str) {
+        self.caves
+            .entry(src.to_string())
+            .or_default()
+            .push(dest.to_string());
+    }
+
+    pub fn part_1(&self) -> Vec<Vec<&str>> {
+        let mut paths = Vec::new();
+        self.walk("start", HashSet::new(), &mut paths, Vec::new(), true);
+        paths
+    }
+
+    pub fn part_2(&self) -> Vec<Vec<&str>> {
+        let mut paths = Vec::new();
+        self.walk("start", HashSet::new(), &mut paths, Vec::new(), false);
+        paths
+    }
+
+    // could make this faster by summing completed paths rather than collecting the actual paths.
+    fn walk<'a>(
+        &'a self,
+        cave: &'a str,
+        mut seen: HashSet<&'a str>,
+        paths: &mut Vec<Vec<&'a str>>,
+        mut path: Vec<&'a str>,
+        mut seen_twice: bool,
+    ) {
+        if seen.contains(cave) {
+            if seen_twice {
+                return;
+            } else {
+                seen_twice = true;
+            }
+        }
+
+        path.push(cave);
+        if cave == "end" {
+            paths.push(path);
+            return;
+        }
+
+        // track small caves we've seen
+        if cave.starts_with(char::is_lowercase) {
+            seen.insert(cave);
+        }
+
+        for neighbour in self.caves[cave].iter().filter(|n| **n != "start") {
+            self.walk(neighbour, seen.clone(), paths, path.clone(), seen_twice);
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+    type Input = Graph;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let edges = parse_lines(input, |l| {
+            let (a, b) = l
+                .split_once('-')
+                .ok_or_else(|| ParseError::new(Self::DAY, l, l, "an edge, e.g. 'start-A'"))?;
+            for cave in [a, b] {
+                if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                    return Err(ParseError::new(Self::DAY, l, cave, "a cave name"));
+                }
+            }
+            // paths could go back and forth between two big caves forever
+            if !a.starts_with(char::is_lowercase) && !b.starts_with(char::is_lowercase) {
+                return Err(ParseError::new(
+                    Self::DAY,
+                    l,
+                    l,
+                    "an edge with a small cave",
+                ));
+            }
+            Ok((a, b))
+        })?;
+
+        let mut graph = Graph::new();
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+
+        for cave in ["start", "end"] {
+            if !graph.caves.contains_key(cave) {
+                let expected = format!("an edge to the {} cave", cave);
+                let end = input.lines().count() + 1;
+                return Err(ParseError::new(Self::DAY, "", "", expected).on_line(end));
+            }
+        }
+        Ok(graph)
+    }
+
+    fn part_1(graph: &Self::Input) -> Self::Part1 {
+        graph.part_1().len()
+    }
+
+    fn part_2(graph: &Self::Input) -> Self::Part2 {
+        graph.part_2().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+    const LARGER: &str = "\
+dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+    const LARGEST: &str = "\
+fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+    #[test]
+    fn walks_each_path_through_the_small_graph() {
+        let graph = Day12::parse(SMALL).unwrap();
+        let mut paths: Vec<_> = graph.part_1().iter().map(|p| p.join(",")).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "start,A,b,A,c,A,end",
+                "start,A,b,A,end",
+                "start,A,b,end",
+                "start,A,c,A,b,A,end",
+                "start,A,c,A,b,end",
+                "start,A,c,A,end",
+                "start,A,end",
+                "start,b,A,c,A,end",
+                "start,b,A,end",
+                "start,b,end",
+            ]
+        );
+    }
+
+    #[test]
+    fn part_1_counts_paths_visiting_small_caves_once() {
+        for (input, paths) in [(SMALL, 10), (LARGER, 19), (LARGEST, 226)] {
+            let graph = Day12::parse(input).unwrap();
+            assert_eq!(Day12::part_1(&graph), paths);
+        }
+    }
+
+    #[test]
+    fn part_2_counts_paths_visiting_a_small_cave_twice() {
+        for (input, paths) in [(SMALL, 36), (LARGER, 103), (LARGEST, 3509)] {
+            let graph = Day12::parse(input).unwrap();
+            assert_eq!(Day12::part_2(&graph), paths);
+        }
+    }
+
+    #[test]
+    fn rejects_graphs_without_a_start_or_with_connected_big_caves() {
+        let error = Day12::parse("a-end").unwrap_err();
+        assert_eq!(error.expected, "an edge to the start cave");
+        let error = Day12::parse("start-A\nA-B\nB-end").unwrap_err();
+        assert_eq!((error.line, error.token.as_str()), (2, "A-B"));
+    }
+}
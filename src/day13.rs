@@ -0,0 +1,365 @@
+use crate::animation::{Animation, Control};
+use crate::error::{parse_token, ParseError};
+use crate::grid::{self, Coord};
+use crate::image::Rgb;
+use crate::solution::{Answer, Solution};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
+/// The furthest fold accepted, which keeps the sheet to a reasonable size
+pub const MAX_FOLD_POSITION: usize = 4999;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Point {
+    Dot,
+    Empty,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Point::Dot => write!(f, "#"),
+            Point::Empty => write!(f, "."),
+        }
+    }
+}
+
+impl Point {
+    pub fn merge(&self, other: &Self) -> Self {
+        if matches!(self, &Point::Dot) || matches!(other, &Point::Dot) {
+            Point::Dot
+        } else {
+            Point::Empty
+        }
+    }
+}
+
+impl FromStr for Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "." => Ok(Self::Empty),
+            "#" => Ok(Self::Dot),
+            _ => Err(ParseError::new(Day13::DAY, s, s, "'.' or '#'")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Direction {
+    X,
+    Y,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::X => write!(f, "x"),
+            Direction::Y => write!(f, "y"),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Self::X),
+            "y" => Ok(Self::Y),
+            _ => Err(ParseError::new(Day13::DAY, s, s, "'x' or 'y'")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub position: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, position) = {
+            let (d, p) = s
+                .strip_prefix("fold along ")
+                .and_then(|f| f.split_once('='))
+                .ok_or_else(|| {
+                    ParseError::new(Day13::DAY, s, s, "a fold, e.g. 'fold along y=7'")
+                })?;
+            let direction = Direction::from_str(d)
+                .map_err(|e| ParseError::new(Day13::DAY, s, d, e.expected))?;
+            let expected = format!("a fold position up to {}", MAX_FOLD_POSITION);
+            match parse_token(Day13::DAY, s, p, &expected)? {
+                position @ 0..=MAX_FOLD_POSITION => (direction, position),
+                _ => return Err(ParseError::new(Day13::DAY, s, p, expected)),
+            }
+        };
+
+        Ok(Self {
+            direction,
+            position,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    points: grid::Grid<Point>,
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.points)
+    }
+}
+
+impl Answer for Grid {}
+
+impl Grid {
+    pub fn new(x: usize, y: usize, coords: Vec<Coord>) -> Self {
+        let mut points = grid::Grid::new(x, y, Point::Empty);
+        for coord in coords {
+            points[coord] = Point::Dot;
+        }
+
+        Self { points }
+    }
+
+    pub fn fold(&mut self, instruction: &Instruction) {
+        let position = instruction.position;
+        let (width, height) = match instruction.direction {
+            Direction::X => (position, self.points.height()),
+            Direction::Y => (self.points.width(), position),
+        };
+
+        let mut folded = grid::Grid::new(width, height, Point::Empty);
+        for ((x, y), point) in self.points.iter() {
+            // points past the fold are mirrored back over it, and the fold itself is removed
+            let coord = match instruction.direction {
+                Direction::X if x > position => (2 * position - x, y),
+                Direction::Y if y > position => (x, 2 * position - y),
+                _ => (x, y),
+            };
+
+            if let Some(p) = folded.get_mut(coord) {
+                *p = p.merge(point);
+            }
+        }
+
+        self.points = folded;
+    }
+
+    /// Animates each fold in turn, starting from the unfolded sheet
+    pub fn animate(
+        &self,
+        instructions: &[Instruction],
+        animation: &mut Animation,
+    ) -> io::Result<()> {
+        let mut grid = self.clone();
+        let caption = format!("unfolded: {} dots", grid.dot_count());
+        if animation.show(&caption, &grid)? == Control::Quit {
+            return Ok(());
+        }
+
+        for (i, instruction) in instructions.iter().enumerate() {
+            grid.fold(instruction);
+            let caption = format!(
+                "fold {} of {} along {}={}: {} dots",
+                i + 1,
+                instructions.len(),
+                instruction.direction,
+                instruction.position,
+                grid.dot_count()
+            );
+
+            if i + 1 == instructions.len() {
+                return animation.finish(&caption, &grid);
+            }
+            if animation.show(&caption, &grid)? == Control::Quit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// An image of the sheet, with dots in black on white
+    pub fn image(&self) -> grid::Grid<Rgb> {
+        self.points.map(|p| match p {
+            Point::Dot => Rgb::BLACK,
+            Point::Empty => Rgb::WHITE,
+        })
+    }
+
+    pub fn points(&self) -> &grid::Grid<Point> {
+        &self.points
+    }
+
+    pub fn dot_count(&self) -> usize {
+        self.points
+            .iter()
+            .filter(|(_, p)| matches!(p, Point::Dot))
+            .count()
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+    type Input = (Grid, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut coords = vec![];
+        let mut lines = input.lines().enumerate();
+        for (i, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+
+            let (x, y) = line.split_once(',').ok_or_else(|| {
+                ParseError::new(Self::DAY, line, line, "a dot, e.g. '6,10'").on_line(i + 1)
+            })?;
+            let coord = (
+                parse_token(Self::DAY, line, x, "an x coordinate").map_err(|e| e.on_line(i + 1))?,
+                parse_token(Self::DAY, line, y, "a y coordinate").map_err(|e| e.on_line(i + 1))?,
+            );
+            coords.push((coord, i, line));
+        }
+
+        let instruction_lines: Vec<_> = lines.collect();
+        let instructions = instruction_lines
+            .iter()
+            .map(|(i, l)| Instruction::from_str(l).map_err(|e| e.on_line(i + 1)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let end = input.lines().count() + 1;
+        let x = {
+            let x_instruction = instructions
+                .iter()
+                .find(|i| matches!(i.direction, Direction::X))
+                .ok_or_else(|| ParseError::new(Self::DAY, "", "", "a fold along x").on_line(end))?;
+            x_instruction.position * 2 + 1
+        };
+        let y = {
+            let y_instruction = instructions
+                .iter()
+                .find(|i| matches!(i.direction, Direction::Y))
+                .ok_or_else(|| ParseError::new(Self::DAY, "", "", "a fold along y").on_line(end))?;
+            y_instruction.position * 2 + 1
+        };
+
+        // the sheet's size is inferred from the first folds, so all dots must lie within it
+        if let Some((_, i, line)) = coords.iter().find(|((cx, cy), _, _)| *cx >= x || *cy >= y) {
+            let expected = format!("a dot within the {}x{} sheet", x, y);
+            return Err(ParseError::new(Self::DAY, line, line, expected).on_line(i + 1));
+        }
+
+        // each fold must be within the sheet, with no more of it past the fold than before it
+        let (mut width, mut height) = (x, y);
+        for (instruction, (i, line)) in instructions.iter().zip(&instruction_lines) {
+            let size = match instruction.direction {
+                Direction::X => &mut width,
+                Direction::Y => &mut height,
+            };
+            if instruction.position >= *size || instruction.position * 2 + 1 < *size {
+                let expected = format!(
+                    "a fold along {} from {} to {}",
+                    instruction.direction,
+                    *size / 2,
+                    *size - 1
+                );
+                return Err(ParseError::new(Self::DAY, line, line, expected).on_line(i + 1));
+            }
+            *size = instruction.position;
+        }
+
+        let coords = coords.into_iter().map(|(c, _, _)| c).collect();
+        Ok((Grid::new(x, y, coords), instructions))
+    }
+
+    fn part_1((grid, instructions): &Self::Input) -> Self::Part1 {
+        let mut grid = grid.clone();
+        grid.fold(&instructions[0]);
+        grid.dot_count()
+    }
+
+    fn part_2((grid, instructions): &Self::Input) -> Self::Part2 {
+        let mut grid = grid.clone();
+        for instruction in instructions {
+            grid.fold(instruction);
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+    #[test]
+    fn part_1_counts_dots_after_the_first_fold() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(input.0.dot_count(), 18);
+        assert_eq!(Day13::part_1(&input), 17);
+    }
+
+    #[test]
+    fn part_2_folds_into_a_square() {
+        let input = Day13::parse(EXAMPLE).unwrap();
+        let grid = Day13::part_2(&input);
+        assert_eq!(grid.dot_count(), 16);
+        assert_eq!(
+            grid.to_string(),
+            "\
+#####
+#...#
+#...#
+#...#
+#####
+.....
+.....
+"
+        );
+    }
+
+    #[test]
+    fn rejects_folds_outside_the_sheet_or_short_of_its_middle() {
+        let error =
+            Day13::parse("0,0\n\nfold along x=5\nfold along y=2\nfold along x=1").unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.expected, "a fold along x from 2 to 4");
+        assert!(Day13::parse("0,0\n\nfold along x=5000\nfold along y=2").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use std::collections::{BTreeMap, HashMap};
+
+/// The number of steps of pair insertion for part 1
+pub const PART_1_STEPS: usize = 10;
+/// The number of steps of pair insertion for part 2
+pub const PART_2_STEPS: usize = 40;
+
+#[derive(Clone)]
+pub struct Polymer {
+    end_chars: (char, char),
+    pairs: HashMap<(char, char), usize>,
+    rules: HashMap<(char, char), char>,
+}
+
+impl Polymer {
+    pub fn new(
+        end_chars: (char, char),
+        pairs: HashMap<(char, char), usize>,
+        rules: HashMap<(char, char), char>,
+    ) -> Self {
+        Self {
+            end_chars,
+            pairs,
+            rules,
+        }
+    }
+
+    pub fn process(&mut self) {
+        self.checked_process().expect("too many elements to count");
+    }
+
+    pub fn output(&self) -> usize {
+        self.checked_output().expect("too many elements to count")
+    }
+
+    /// The output after each of a number of steps, in the order of the steps. Once there are too
+    /// many elements to count, the outputs are `None`.
+    pub fn outputs_after(&self, steps: &[usize]) -> Vec<(usize, Option<usize>)> {
+        let mut steps = steps.to_vec();
+        steps.sort_unstable();
+        let mut polymer = Some(self.clone());
+        let mut step = 0;
+        steps
+            .into_iter()
+            .map(|s| {
+                while step < s {
+                    if polymer
+                        .as_mut()
+                        .and_then(Polymer::checked_process)
+                        .is_none()
+                    {
+                        polymer = None;
+                    }
+                    step += 1;
+                }
+                (s, polymer.as_ref().and_then(Polymer::checked_output))
+            })
+            .collect()
+    }
+
+    fn checked_process(&mut self) -> Option<()> {
+        let pairs = self.pairs.drain().collect::<Vec<_>>();
+        let mut new_pairs = HashMap::new();
+        let mut add = |pair, count| {
+            let total: &mut usize = new_pairs.entry(pair).or_insert(0);
+            *total = total.checked_add(count)?;
+            Some(())
+        };
+        for (pair @ (a, b), count) in pairs {
+            match self.rules.get(&pair) {
+                Some(&c) => {
+                    add((a, c), count)?;
+                    add((c, b), count)?;
+                }
+                None => add(pair, count)?,
+            }
+        }
+        self.pairs = new_pairs;
+        Some(())
+    }
+
+    fn checked_output(&self) -> Option<usize> {
+        let mut counts = BTreeMap::new();
+        let mut add = |c, count| {
+            let total: &mut usize = counts.entry(c).or_insert(0);
+            *total = total.checked_add(count)?;
+            Some(())
+        };
+        for ((a, b), count) in &self.pairs {
+            add(*a, *count)?;
+            add(*b, *count)?;
+        }
+
+        // all chars will be doubled *except* the first and last char of the original template.
+        // add these in
+        add(self.end_chars.0, 1)?;
+        add(self.end_chars.1, 1)?;
+        Some((counts.values().max()? - counts.values().min()?) / 2)
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+    type Input = Polymer;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let is_element =
+            |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_uppercase());
+        let mut lines = input.lines();
+        let template_line = lines.next().unwrap_or_default();
+        if template_line.len() < 2 || !is_element(template_line, template_line.len()) {
+            return Err(ParseError::new(
+                Self::DAY,
+                template_line,
+                template_line,
+                "a template of at least 2 elements",
+            ));
+        }
+
+        let mut template = template_line.chars();
+        let pairs = {
+            let mut h = HashMap::new();
+            for c in template
+                .clone()
+                .collect::<Vec<_>>()
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+            {
+                *h.entry(c).or_insert(0) += 1;
+            }
+            h
+        };
+
+        if let Some(l) = lines.next().filter(|l| !l.is_empty()) {
+            return Err(ParseError::new(Self::DAY, l, l, "an empty line").on_line(2));
+        }
+
+        let rules: HashMap<_, _> = lines
+            .enumerate()
+            .map(|(i, l)| {
+                let error =
+                    |token, expected| ParseError::new(Self::DAY, l, token, expected).on_line(i + 3);
+                let (input, output) = l
+                    .split_once(" -> ")
+                    .ok_or_else(|| error(l, "a rule, e.g. 'CH -> B'"))?;
+                if !is_element(input, 2) {
+                    return Err(error(input, "a pair of elements"));
+                }
+                if !is_element(output, 1) {
+                    return Err(error(output, "an element"));
+                }
+
+                let mut input_chars = input.chars();
+                Ok((
+                    (input_chars.next().unwrap(), input_chars.next().unwrap()),
+                    output.chars().next().unwrap(),
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let end_chars = (template.next().unwrap(), template.last().unwrap());
+
+        Ok(Polymer::new(end_chars, pairs, rules))
+    }
+
+    fn part_1(polymer: &Self::Input) -> Self::Part1 {
+        let mut polymer = polymer.clone();
+        for _ in 0..PART_1_STEPS {
+            polymer.process();
+        }
+
+        polymer.output()
+    }
+
+    fn part_2(polymer: &Self::Input) -> Self::Part2 {
+        let mut polymer = polymer.clone();
+        for _ in 0..PART_2_STEPS {
+            polymer.process();
+        }
+
+        polymer.output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+    fn pair_counts(polymer: &str) -> HashMap<(char, char), usize> {
+        let chars: Vec<_> = polymer.chars().collect();
+        let mut pairs = HashMap::new();
+        for w in chars.windows(2) {
+            *pairs.entry((w[0], w[1])).or_insert(0) += 1;
+        }
+        pairs
+    }
+
+    #[test]
+    fn counts_pairs_after_each_step() {
+        let mut polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(polymer.pairs, pair_counts("NNCB"));
+        for expected in [
+            "NCNBCHB",
+            "NBCCNBBBCBHCB",
+            "NBBBCNCCNBBNBNBBCHBHHBCHB",
+            "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB",
+        ] {
+            polymer.process();
+            assert_eq!(polymer.pairs, pair_counts(expected));
+        }
+    }
+
+    #[test]
+    fn part_1_subtracts_element_counts_after_10_steps() {
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part_1(&polymer), 1588);
+    }
+
+    #[test]
+    fn part_2_subtracts_element_counts_after_40_steps() {
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part_2(&polymer), 2188189693529);
+    }
+
+    #[test]
+    fn subtracts_element_counts_at_each_checkpoint() {
+        let polymer = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            polymer.outputs_after(&[40, 10, 0]),
+            [(0, Some(1)), (10, Some(1588)), (40, Some(2188189693529))]
+        );
+        assert_eq!(polymer.outputs_after(&[70])[0], (70, None));
+    }
+
+    /// Builds the polymer one step at a time, then subtracts the least common element's count
+    /// from the most common's
+    fn grow(template: &str, rules: &HashMap<(char, char), char>, steps: usize) -> usize {
+        let mut polymer: Vec<char> = template.chars().collect();
+        for _ in 0..steps {
+            let mut grown = vec![polymer[0]];
+            for w in polymer.windows(2) {
+                grown.extend(rules.get(&(w[0], w[1])));
+                grown.push(w[1]);
+            }
+            polymer = grown;
+        }
+
+        let mut counts = HashMap::new();
+        for c in polymer {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+        counts.values().max().unwrap() - counts.values().min().unwrap()
+    }
+
+    /// A template of a few elements, with rules for some of the pairs of them
+    fn polymer() -> impl Strategy<Value = (String, HashMap<(char, char), char>)> {
+        let elements = || prop::sample::select(vec!['A', 'B', 'C', 'D']);
+        (
+            prop::collection::vec(elements(), 2..8).prop_map(|t| t.into_iter().collect()),
+            prop::collection::hash_map((elements(), elements()), elements(), 0..16),
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn counting_pairs_matches_growing_the_polymer(
+            (template, rules) in polymer(),
+            steps in 0..8usize,
+        ) {
+            let mut input = format!("{}\n", template);
+            for ((a, b), c) in &rules {
+                input.push_str(&format!("\n{}{} -> {}", a, b, c));
+            }
+
+            let mut polymer = Day14::parse(&input).unwrap();
+            for _ in 0..steps {
+                polymer.process();
+            }
+            prop_assert_eq!(polymer.output(), grow(&template, &rules, steps));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::animation::{Animation, Control};
+use crate::error::ParseError;
+use crate::grid::{self, Coord};
+use crate::image::{Overlay, Rgb};
+use crate::solution::Solution;
+use ansi_term::Colour;
+use pathfinding::prelude::{build_path, dijkstra, dijkstra_all};
+use std::io;
+use std::str::FromStr;
+
+#[derive(Clone)]
+pub struct Grid(grid::Grid<usize>);
+
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let risks = grid::Grid::parse_digits(Day15::DAY, s)?;
+        Ok(Self(risks.map(|&r| r as usize)))
+    }
+}
+
+impl Grid {
+    pub fn end(&self) -> Coord {
+        (self.0.width() - 1, self.0.height() - 1)
+    }
+
+    pub fn shortest_path(&self, start: &Coord) -> usize {
+        self.search(start).1
+    }
+
+    /// The positions along the lowest risk path from the start to the end, including both
+    pub fn lowest_risk_path(&self, start: &Coord) -> Vec<Coord> {
+        self.search(start).0
+    }
+
+    fn search(&self, start: &Coord) -> (Vec<Coord>, usize) {
+        let end = self.end();
+        dijkstra(start, |&p| self.successors(p), |p| *p == end).unwrap()
+    }
+
+    fn successors(&self, coord: Coord) -> impl Iterator<Item = (Coord, usize)> + '_ {
+        self.0.neighbours(coord).map(move |n| (n, self.0[n]))
+    }
+
+    /// An image of the risk levels, optionally with the lowest risk path from the top left in red
+    pub fn image(&self, overlays: &[Overlay]) -> grid::Grid<Rgb> {
+        let mut image = self.0.map(|&r| Rgb::scale(r, 9));
+        if overlays.contains(&Overlay::Path) {
+            for coord in self.lowest_risk_path(&(0, 0)) {
+                image[coord] = Rgb::RED;
+            }
+        }
+        image
+    }
+
+    /// Animates the search for the lowest risk path as the total risk grows, showing positions
+    /// already reached in blue and those just reached in yellow, then the path in red
+    pub fn animate(&self, start: &Coord, animation: &mut Animation) -> io::Result<()> {
+        let parents = dijkstra_all(start, |&p| self.successors(p));
+        let mut risks = self.0.map(|_| None);
+        risks[*start] = Some(0);
+        for (coord, (_, risk)) in &parents {
+            risks[*coord] = Some(*risk);
+        }
+
+        let end = self.end();
+        let total = risks[end].unwrap_or(0);
+        // around 100 frames, whatever the size of the grid
+        let step = (total / 100).max(1);
+        for limit in (0..total).step_by(step) {
+            let frame = risks.map(|risk| match risk {
+                Some(r) if *r + step <= limit => Colour::Blue.paint("#").to_string(),
+                Some(r) if *r <= limit => Colour::Yellow.bold().paint("#").to_string(),
+                _ => ".".to_string(),
+            });
+
+            let caption = format!("total risk {} of {}", limit, total);
+            if animation.show(&caption, &frame)? == Control::Quit {
+                return Ok(());
+            }
+        }
+
+        let path = build_path(&end, &parents);
+        let mut frame = risks.map(|risk| match risk {
+            Some(r) if *r <= total => Colour::Blue.paint("#").to_string(),
+            _ => ".".to_string(),
+        });
+        for coord in &path {
+            frame[*coord] = Colour::Red.bold().paint("#").to_string();
+        }
+        let caption = format!(
+            "lowest total risk {} along a path of {} positions",
+            total,
+            path.len()
+        );
+        animation.finish(&caption, &frame)
+    }
+
+    pub fn expand(&mut self) {
+        let (width, height) = (self.0.width(), self.0.height());
+        self.0 = grid::Grid::from_fn(width * 5, height * 5, |(x, y)| {
+            let mut n = self.0[(x % width, y % height)] + x / width + y / height;
+            if n > 9 {
+                n -= 9;
+            }
+            n
+        });
+    }
+
+    pub fn risks(&self) -> &grid::Grid<usize> {
+        &self.0
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::from_str(input)
+    }
+
+    fn part_1(grid: &Self::Input) -> Self::Part1 {
+        grid.shortest_path(&(0, 0))
+    }
+
+    fn part_2(grid: &Self::Input) -> Self::Part2 {
+        let mut grid = grid.clone();
+        grid.expand();
+        grid.shortest_path(&(0, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn part_1_finds_the_lowest_total_risk() {
+        let grid = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part_1(&grid), 40);
+    }
+
+    #[test]
+    fn expands_the_grid_five_times_in_each_direction() {
+        let mut grid = Day15::parse(EXAMPLE).unwrap();
+        grid.expand();
+        let risks = grid.risks();
+        assert_eq!((risks.width(), risks.height()), (50, 50));
+        assert_eq!(risks[(10, 0)], 2);
+        assert_eq!(risks[(49, 49)], 9);
+    }
+
+    #[test]
+    fn part_2_finds_the_lowest_total_risk_in_the_expanded_grid() {
+        let grid = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part_2(&grid), 315);
+    }
+}
@@ -0,0 +1,455 @@
+use crate::error::ParseError;
+use crate::solution::{Checked, Solution};
+use std::fmt::{Display, Formatter};
+
+/// A stream of bits read from a line of hex digits, most significant bit first
+pub struct Bits<'a> {
+    hex: &'a str,
+    position: usize,
+}
+
+impl<'a> Bits<'a> {
+    pub fn new(hex: &'a str) -> Result<Self, ParseError> {
+        if let Some(i) = hex.find(|c: char| !c.is_ascii_hexdigit()) {
+            let end = i + hex[i..].chars().next().unwrap().len_utf8();
+            return Err(ParseError::new(
+                Day16::DAY,
+                hex,
+                &hex[i..end],
+                "a hex digit",
+            ));
+        }
+
+        Ok(Self { hex, position: 0 })
+    }
+
+    /// The number of bits read so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of bits left to read
+    pub fn remaining(&self) -> usize {
+        self.hex.len() * 4 - self.position
+    }
+
+    /// Reads a number of bits, up to 64, as a number, or `None` if there aren't that many left
+    pub fn read(&mut self, bits: usize) -> Option<u64> {
+        if bits > 64 || bits > self.remaining() {
+            return None;
+        }
+
+        let digits = self.hex.as_bytes();
+        let mut value = 0;
+        for _ in 0..bits {
+            let digit = (digits[self.position / 4] as char).to_digit(16).unwrap();
+            let bit = (digit >> (3 - self.position % 4)) & 1;
+            value = value << 1 | bit as u64;
+            self.position += 1;
+        }
+        Some(value)
+    }
+
+    /// An error pointing at the hex digit holding a bit, or at the end of the line if the bit is
+    /// past it
+    fn error_at(&self, position: usize, expected: impl Into<String>) -> ParseError {
+        let i = (position / 4).min(self.hex.len());
+        let end = (i + 1).min(self.hex.len());
+        ParseError::new(Day16::DAY, self.hex, &self.hex[i..end], expected)
+    }
+
+    fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.position, expected)
+    }
+}
+
+/// What an operator packet does with the values of its sub-packets
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    /// 1 if the first value is greater than the second, otherwise 0
+    GreaterThan,
+    /// 1 if the first value is less than the second, otherwise 0
+    LessThan,
+    /// 1 if the two values are equal, otherwise 0
+    EqualTo,
+}
+
+impl Operator {
+    /// The operator with a packet type ID, or `None` for 4, which is a literal value
+    pub fn from_type_id(type_id: u8) -> Option<Self> {
+        match type_id {
+            0 => Some(Self::Sum),
+            1 => Some(Self::Product),
+            2 => Some(Self::Minimum),
+            3 => Some(Self::Maximum),
+            5 => Some(Self::GreaterThan),
+            6 => Some(Self::LessThan),
+            7 => Some(Self::EqualTo),
+            _ => None,
+        }
+    }
+
+    pub fn type_id(&self) -> u8 {
+        match self {
+            Self::Sum => 0,
+            Self::Product => 1,
+            Self::Minimum => 2,
+            Self::Maximum => 3,
+            Self::GreaterThan => 5,
+            Self::LessThan => 6,
+            Self::EqualTo => 7,
+        }
+    }
+
+    /// Whether the operator compares exactly two values
+    pub fn is_comparison(&self) -> bool {
+        matches!(self, Self::GreaterThan | Self::LessThan | Self::EqualTo)
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sum => write!(f, "sum"),
+            Self::Product => write!(f, "product"),
+            Self::Minimum => write!(f, "min"),
+            Self::Maximum => write!(f, "max"),
+            Self::GreaterThan => write!(f, "gt"),
+            Self::LessThan => write!(f, "lt"),
+            Self::EqualTo => write!(f, "eq"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Contents {
+    Literal(u64),
+    Operator(Operator, Vec<Packet>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Packet {
+    pub version: u8,
+    pub contents: Contents,
+}
+
+/// Packets are shown as expressions, e.g. `sum(1, product(2, 3))`
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.contents {
+            Contents::Literal(value) => write!(f, "{}", value),
+            Contents::Operator(operator, packets) => {
+                write!(f, "{}(", operator)?;
+                for (i, packet) in packets.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", packet)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Sub-packets are nested no deeper than this, as reading, showing and evaluating a packet all
+/// recurse into its sub-packets
+pub const MAX_DEPTH: usize = 100;
+
+impl Packet {
+    /// Reads a packet, with any sub-packets, from the bits
+    pub fn read(bits: &mut Bits) -> Result<Self, ParseError> {
+        Self::read_nested(bits, 0)
+    }
+
+    fn read_nested(bits: &mut Bits, depth: usize) -> Result<Self, ParseError> {
+        let start = bits.position();
+        if depth > MAX_DEPTH {
+            let expected = format!("sub-packets nested no deeper than {}", MAX_DEPTH);
+            return Err(bits.error(expected));
+        }
+        let version = bits.read(3).ok_or_else(|| bits.error("a packet version"))? as u8;
+        let type_id = bits.read(3).ok_or_else(|| bits.error("a packet type ID"))? as u8;
+
+        let operator = match Operator::from_type_id(type_id) {
+            Some(o) => o,
+            None => {
+                return Ok(Self {
+                    version,
+                    contents: Contents::Literal(Self::read_literal(bits)?),
+                })
+            }
+        };
+
+        let mut packets = vec![];
+        match bits.read(1).ok_or_else(|| bits.error("a length type ID"))? {
+            0 => {
+                let length = bits
+                    .read(15)
+                    .ok_or_else(|| bits.error("a 15 bit length of sub-packets"))?
+                    as usize;
+                let end = bits.position() + length;
+                while bits.position() < end {
+                    packets.push(Self::read_nested(bits, depth + 1)?);
+                }
+                if bits.position() != end {
+                    return Err(
+                        bits.error_at(start, format!("sub-packets that fill {} bits", length))
+                    );
+                }
+            }
+            _ => {
+                let count = bits
+                    .read(11)
+                    .ok_or_else(|| bits.error("an 11 bit count of sub-packets"))?;
+                for _ in 0..count {
+                    packets.push(Self::read_nested(bits, depth + 1)?);
+                }
+            }
+        }
+
+        if packets.is_empty() {
+            return Err(bits.error_at(start, "an operator with sub-packets"));
+        }
+        if operator.is_comparison() && packets.len() != 2 {
+            return Err(bits.error_at(start, "a comparison of two sub-packets"));
+        }
+
+        Ok(Self {
+            version,
+            contents: Contents::Operator(operator, packets),
+        })
+    }
+
+    /// Reads groups of 5 bits until one starts with 0, with the last 4 bits of each group being
+    /// the next 4 bits of the value
+    fn read_literal(bits: &mut Bits) -> Result<u64, ParseError> {
+        let start = bits.position();
+        let mut value: u64 = 0;
+        loop {
+            let group = bits
+                .read(5)
+                .ok_or_else(|| bits.error("a group of a literal value"))?;
+            if value.leading_zeros() < 4 {
+                return Err(bits.error_at(start, "a literal value that fits in 64 bits"));
+            }
+            value = value << 4 | group & 0xf;
+            if group & 0x10 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// The sum of the versions of this packet and all its sub-packets
+    pub fn version_sum(&self) -> u64 {
+        let sub_packets = match &self.contents {
+            Contents::Literal(_) => 0,
+            Contents::Operator(_, packets) => packets.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as u64 + sub_packets
+    }
+
+    /// The value of the expression the packet represents, or `None` if it or any sub-expression
+    /// overflows
+    pub fn value(&self) -> Option<u64> {
+        let (operator, packets) = match &self.contents {
+            Contents::Literal(value) => return Some(*value),
+            Contents::Operator(operator, packets) => (operator, packets),
+        };
+
+        let mut values = packets.iter().map(Packet::value);
+        Some(match operator {
+            Operator::Sum => values.try_fold(0u64, |sum, v| sum.checked_add(v?))?,
+            Operator::Product => values.try_fold(1u64, |product, v| product.checked_mul(v?))?,
+            Operator::Minimum => values.collect::<Option<Vec<_>>>()?.into_iter().min()?,
+            Operator::Maximum => values.collect::<Option<Vec<_>>>()?.into_iter().max()?,
+            comparison => {
+                let (a, b) = (values.next()??, values.next()??);
+                let result = match comparison {
+                    Operator::GreaterThan => a > b,
+                    Operator::LessThan => a < b,
+                    _ => a == b,
+                };
+                result as u64
+            }
+        })
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+    type Input = Packet;
+    type Part1 = u64;
+    type Part2 = Checked<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input.lines();
+        let hex = lines.next().unwrap_or_default();
+        if let Some(l) = lines.next() {
+            return Err(ParseError::new(Self::DAY, l, l, "a single line of hex digits").on_line(2));
+        }
+
+        let mut bits = Bits::new(hex)?;
+        let packet = Packet::read(&mut bits)?;
+
+        // the transmission is padded to a whole number of hex digits with zero bits
+        while bits.remaining() > 0 {
+            let position = bits.position();
+            if bits.read(bits.remaining().min(64)) != Some(0) {
+                return Err(bits.error_at(position, "only zero bits after the packet"));
+            }
+        }
+
+        Ok(packet)
+    }
+
+    fn part_1(packet: &Self::Input) -> Self::Part1 {
+        packet.version_sum()
+    }
+
+    fn part_2(packet: &Self::Input) -> Self::Part2 {
+        packet.value().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(version: u8, value: u64) -> Packet {
+        Packet {
+            version,
+            contents: Contents::Literal(value),
+        }
+    }
+
+    #[test]
+    fn reads_bits_from_hex() {
+        let mut bits = Bits::new("D2FE28").unwrap();
+        assert_eq!(bits.read(3), Some(0b110));
+        assert_eq!(bits.read(3), Some(0b100));
+        assert_eq!(bits.read(15), Some(0b10111_11110_00101));
+        assert_eq!(bits.remaining(), 3);
+        assert_eq!(bits.read(4), None);
+        assert_eq!(bits.read(3), Some(0));
+    }
+
+    #[test]
+    fn parses_literal_packets() {
+        assert_eq!(Day16::parse("D2FE28").unwrap(), literal(6, 2021));
+    }
+
+    #[test]
+    fn parses_operator_packets_with_either_length_type() {
+        assert_eq!(
+            Day16::parse("38006F45291200").unwrap(),
+            Packet {
+                version: 1,
+                contents: Contents::Operator(
+                    Operator::LessThan,
+                    vec![literal(6, 10), literal(2, 20)]
+                ),
+            }
+        );
+        assert_eq!(
+            Day16::parse("EE00D40C823060").unwrap(),
+            Packet {
+                version: 7,
+                contents: Contents::Operator(
+                    Operator::Maximum,
+                    vec![literal(2, 1), literal(4, 2), literal(1, 3)]
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn part_1_sums_versions() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(Day16::part_1(&Day16::parse(hex).unwrap()), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn part_2_evaluates_the_expression() {
+        for (hex, expression, value) in [
+            ("C200B40A82", "sum(1, 2)", 3),
+            ("04005AC33890", "product(6, 9)", 54),
+            ("880086C3E88112", "min(7, 8, 9)", 7),
+            ("CE00C43D881120", "max(7, 8, 9)", 9),
+            ("D8005AC2A8F0", "lt(5, 15)", 1),
+            ("F600BC2D8F", "gt(5, 15)", 0),
+            ("9C005AC2F8F0", "eq(5, 15)", 0),
+            (
+                "9C0141080250320F1802104A08",
+                "eq(sum(1, 3), product(2, 2))",
+                1,
+            ),
+        ] {
+            let packet = Day16::parse(hex).unwrap();
+            assert_eq!(packet.to_string(), expression);
+            assert_eq!(Day16::part_2(&packet), Checked::Answer(value), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_transmissions() {
+        let error = |hex| Day16::parse(hex).unwrap_err().to_string();
+        assert_eq!(
+            error("D2FG28"),
+            "day 16, line 1, column 4: expected a hex digit, found 'G'"
+        );
+        assert_eq!(
+            error("D2FE"),
+            "day 16, line 1, column 5: expected a group of a literal value, found nothing"
+        );
+        assert_eq!(
+            error("D2FE2F"),
+            "day 16, line 1, column 6: expected only zero bits after the packet, found 'F'"
+        );
+        // a sum, counting zero sub-packets
+        assert_eq!(
+            error("02000"),
+            "day 16, line 1, column 1: expected an operator with sub-packets, found '0'"
+        );
+        // less than, with a single sub-packet
+        assert_eq!(
+            error("3A005450"),
+            "day 16, line 1, column 1: expected a comparison of two sub-packets, found '3'"
+        );
+    }
+
+    #[test]
+    fn rejects_packets_nested_too_deeply() {
+        // sums of a single sub-packet, one inside the next, around a literal 0
+        let nested = |depth| {
+            let mut bits = "000000100000000001".repeat(depth) + "00010000000";
+            bits += &"0".repeat((4 - bits.len() % 4) % 4);
+            (0..bits.len())
+                .step_by(4)
+                .map(|i| format!("{:X}", u8::from_str_radix(&bits[i..i + 4], 2).unwrap()))
+                .collect::<String>()
+        };
+
+        let packet = Day16::parse(&nested(MAX_DEPTH)).unwrap();
+        assert_eq!(Day16::part_2(&packet), Checked::Answer(0));
+        // the literal inside the 101st sum starts at bit 1818, in the 455th hex digit
+        assert_eq!(
+            Day16::parse(&nested(MAX_DEPTH + 1))
+                .unwrap_err()
+                .to_string(),
+            "day 16, line 1, column 455: expected sub-packets nested no deeper than 100, found '4'"
+        );
+    }
+}
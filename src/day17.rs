@@ -0,0 +1,406 @@
+use crate::error::{parse_token, ParseError};
+use crate::grid;
+use crate::solution::{Checked, Solution};
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// The furthest from the launch position, in x or y, that the search for velocities that hit the
+/// target goes
+pub const MAX_SEARCH: i64 = 1_000_000;
+
+pub type Position = (i64, i64);
+
+pub type Velocity = (i64, i64);
+
+/// A probe launched from `(0, 0)`. As an iterator, it yields the probe's position after each
+/// step, forever.
+#[derive(Debug, Clone)]
+pub struct Probe {
+    position: Position,
+    velocity: Velocity,
+}
+
+impl Probe {
+    pub fn new(velocity: Velocity) -> Self {
+        Self {
+            position: (0, 0),
+            velocity,
+        }
+    }
+}
+
+impl Iterator for Probe {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vx, vy) = self.velocity;
+        self.position = (self.position.0 + vx, self.position.1 + vy);
+        // drag slows the probe towards no horizontal movement, and gravity pulls it down
+        self.velocity = (vx - vx.signum(), vy - 1);
+        Some(self.position)
+    }
+}
+
+/// The target area, which the search expects to the right of and below the launch position
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Target {
+    pub x: RangeInclusive<i64>,
+    pub y: RangeInclusive<i64>,
+}
+
+impl FromStr for Target {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .strip_prefix("target area: x=")
+            .and_then(|t| t.split_once(", y="))
+            .ok_or_else(|| {
+                ParseError::new(
+                    Day17::DAY,
+                    s,
+                    s,
+                    "a target area, e.g. 'target area: x=20..30, y=-10..-5'",
+                )
+            })?;
+
+        let range = |r: &str, expected: &str| {
+            let (start, end) = r
+                .split_once("..")
+                .ok_or_else(|| ParseError::new(Day17::DAY, s, r, "a range, e.g. '20..30'"))?;
+            let (start, end) = (
+                parse_token(Day17::DAY, s, start, expected)?,
+                parse_token(Day17::DAY, s, end, expected)?,
+            );
+            if start > end {
+                return Err(ParseError::new(
+                    Day17::DAY,
+                    s,
+                    r,
+                    "a range from the lowest to the highest coordinate",
+                ));
+            }
+            Ok(start..=end)
+        };
+
+        Ok(Self {
+            x: range(x, "an x coordinate")?,
+            y: range(y, "a y coordinate")?,
+        })
+    }
+}
+
+impl Target {
+    pub fn contains(&self, (x, y): Position) -> bool {
+        self.x.contains(&x) && self.y.contains(&y)
+    }
+
+    /// Whether a probe at the position can never reach the target, being beyond it or below it
+    pub fn is_passed(&self, (x, y): Position) -> bool {
+        x > *self.x.end() || y < *self.y.start()
+    }
+
+    /// The positions of a probe until it's in the target, or until it passes it
+    pub fn trajectory(&self, velocity: Velocity) -> impl Iterator<Item = Position> + '_ {
+        let mut done = false;
+        Probe::new(velocity).take_while(move |&p| {
+            let take = !done;
+            done = self.contains(p) || self.is_passed(p);
+            take
+        })
+    }
+
+    /// The highest a probe launched with the velocity reaches, if it ends up in the target
+    pub fn peak(&self, velocity: Velocity) -> Option<i64> {
+        let mut peak = 0;
+        for position in self.trajectory(velocity) {
+            peak = peak.max(position.1);
+            if self.contains(position) {
+                return Some(peak);
+            }
+        }
+        None
+    }
+
+    /// The first and last steps the probe is over the target, the last `i64::MAX` if it stops
+    fn x_steps(&self, mut vx: i64) -> Option<(i64, i64)> {
+        let (mut x, mut step, mut first) = (0, 0, None);
+        while vx > 0 {
+            x += vx;
+            vx -= 1;
+            step += 1;
+            if x > *self.x.end() {
+                return first.map(|first| (first, step - 1));
+            }
+            if x >= *self.x.start() && first.is_none() {
+                first = Some(step);
+            }
+        }
+        first.map(|first| (first, i64::MAX))
+    }
+
+    /// The first and last steps the probe is level with the target
+    fn y_steps(&self, vy: i64) -> Option<(i64, i64)> {
+        // a probe launched upwards is back at y = 0 after 2 * vy + 1 steps, falling at vy + 1
+        let (mut y, mut vy, mut step) = match vy {
+            vy if vy > 0 => (0, -vy - 1, 2 * vy + 1),
+            vy => (0, vy, 0),
+        };
+        let mut first = None;
+        loop {
+            y += vy;
+            vy -= 1;
+            step += 1;
+            if y < *self.y.start() {
+                return first.map(|first| (first, step - 1));
+            }
+            if y <= *self.y.end() && first.is_none() {
+                first = Some(step);
+            }
+        }
+    }
+
+    /// Each vertical velocity that hits the target, with how many horizontal velocities it hits with
+    pub fn hits(&self) -> Checked<Vec<(i64, usize)>> {
+        let (x_max, y_min) = (*self.x.end(), *self.y.start());
+        if *self.x.start() < 1 || *self.y.end() > -1 {
+            return Checked::NoAnswer("the target isn't right of and below the launch");
+        }
+        if x_max > MAX_SEARCH || y_min < -MAX_SEARCH {
+            return Checked::NoAnswer("the target is too far to search");
+        }
+
+        let (mut firsts, mut lasts): (Vec<_>, Vec<_>) =
+            (1..=x_max).filter_map(|vx| self.x_steps(vx)).unzip();
+        firsts.sort_unstable();
+        lasts.sort_unstable();
+
+        // any faster, and the probe passes below the target on the first step down from y = 0
+        let hits = (y_min..-y_min).filter_map(|vy| {
+            let (first, last) = self.y_steps(vy)?;
+            // the horizontal velocities over the target at some step from first to last
+            let hits =
+                firsts.partition_point(|&f| f <= last) - lasts.partition_point(|&l| l < first);
+            Some((vy, hits)).filter(|_| hits > 0)
+        });
+        Checked::Answer(hits.collect())
+    }
+
+    /// Draws the trajectory, with the launch position as `S`, the probe as `#` and the target
+    /// area as `T`, from the top left
+    pub fn draw(&self, velocity: Velocity) -> grid::Grid<Cell> {
+        let trajectory: Vec<_> = self.trajectory(velocity).collect();
+        let xs = trajectory.iter().map(|p| p.0);
+        let ys = trajectory.iter().map(|p| p.1);
+        let (x_min, x_max) = (
+            xs.clone().min().unwrap_or(0).min(0),
+            xs.max().unwrap_or(0).max(*self.x.end()),
+        );
+        let (y_min, y_max) = (
+            ys.clone().min().unwrap_or(0).min(*self.y.start()),
+            ys.max().unwrap_or(0).max(0),
+        );
+
+        let width = (x_max - x_min + 1) as usize;
+        let height = (y_max - y_min + 1) as usize;
+        let mut cells = grid::Grid::from_fn(width, height, |(x, y)| {
+            let position = (x as i64 + x_min, y_max - y as i64);
+            if position == (0, 0) {
+                Cell::Start
+            } else if self.contains(position) {
+                Cell::Target
+            } else {
+                Cell::Empty
+            }
+        });
+        for (x, y) in trajectory {
+            cells[((x - x_min) as usize, (y_max - y) as usize)] = Cell::Probe;
+        }
+        cells
+    }
+}
+
+/// A cell of a drawn trajectory
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Cell {
+    Start,
+    Probe,
+    Target,
+    Empty,
+}
+
+impl Display for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Start => write!(f, "S"),
+            Cell::Probe => write!(f, "#"),
+            Cell::Target => write!(f, "T"),
+            Cell::Empty => write!(f, "."),
+        }
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+    type Input = Target;
+    type Part1 = Checked<i64>;
+    type Part2 = Checked<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input.lines();
+        let target = Target::from_str(lines.next().unwrap_or_default())?;
+        if let Some(l) = lines.next() {
+            return Err(ParseError::new(Self::DAY, l, l, "a single target area").on_line(2));
+        }
+        Ok(target)
+    }
+
+    fn part_1(target: &Self::Input) -> Self::Part1 {
+        // launching straight into the target's top left corner always hits it
+        target.hits().map(|hits| {
+            let vy = hits.iter().map(|&(vy, _)| vy).max().unwrap();
+            if vy > 0 {
+                vy * (vy + 1) / 2
+            } else {
+                0
+            }
+        })
+    }
+
+    fn part_2(target: &Self::Input) -> Self::Part2 {
+        target
+            .hits()
+            .map(|hits| hits.iter().map(|&(_, hits)| hits).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "target area: x=20..30, y=-10..-5";
+
+    #[test]
+    fn steps_the_probe_with_drag_and_gravity() {
+        let positions: Vec<_> = Probe::new((2, 1)).take(4).collect();
+        assert_eq!(positions, [(2, 1), (3, 1), (3, 0), (3, -2)]);
+    }
+
+    #[test]
+    fn finds_velocities_that_hit_the_target() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(target.peak((7, 2)), Some(3));
+        assert_eq!(target.peak((6, 3)), Some(6));
+        assert_eq!(target.peak((9, 0)), Some(0));
+        assert_eq!(target.peak((17, -4)), None);
+    }
+
+    #[test]
+    fn draws_the_trajectory() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            target.draw((7, 2)).to_string(),
+            "\
+.............#....#............
+.......#..............#........
+...............................
+S........................#.....
+...............................
+...............................
+...........................#...
+...............................
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+....................TTTTTTTT#TT
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+"
+        );
+    }
+
+    #[test]
+    fn part_1_finds_the_highest_peak() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part_1(&target), Checked::Answer(45));
+    }
+
+    #[test]
+    fn part_2_counts_velocities_that_hit() {
+        let target = Day17::parse(EXAMPLE).unwrap();
+        assert_eq!(Day17::part_2(&target), Checked::Answer(112));
+    }
+
+    #[test]
+    fn finds_the_same_hits_as_trying_every_velocity() {
+        for input in [
+            EXAMPLE,
+            "target area: x=1..1, y=-1..-1",
+            "target area: x=5..40, y=-30..-2",
+            "target area: x=60..61, y=-3..-1",
+        ] {
+            let target = Day17::parse(input).unwrap();
+            let (x_max, y_min) = (*target.x.end(), *target.y.start());
+            let peaks: Vec<_> = (1..=x_max)
+                .flat_map(|vx| (y_min..-y_min).map(move |vy| (vx, vy)))
+                .filter_map(|v| target.peak(v))
+                .collect();
+            let highest = *peaks.iter().max().unwrap();
+            assert_eq!(
+                Day17::part_1(&target),
+                Checked::Answer(highest),
+                "{}",
+                input
+            );
+            assert_eq!(
+                Day17::part_2(&target),
+                Checked::Answer(peaks.len()),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn searches_distant_targets() {
+        // 707 is the slowest x velocity that reaches the target, stopping over it
+        let target = Day17::parse("target area: x=250000..300000, y=-300000..-250000").unwrap();
+        assert_eq!(
+            Day17::part_1(&target),
+            Checked::Answer(300_000 * 299_999 / 2)
+        );
+
+        let target = Day17::parse("target area: x=20..30, y=-10..5").unwrap();
+        assert_eq!(
+            Day17::part_2(&target).to_string(),
+            "the target isn't right of and below the launch"
+        );
+        let target = Day17::parse("target area: x=20..1000001, y=-10..-5").unwrap();
+        assert_eq!(
+            Day17::part_1(&target).to_string(),
+            "the target is too far to search"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_targets() {
+        let error = |input| Day17::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("target area: x=20..30, y=-10..five"),
+            "day 17, line 1, column 31: expected a y coordinate, found 'five'"
+        );
+        assert_eq!(
+            error("target area: x=30..20, y=-10..-5"),
+            "day 17, line 1, column 16: expected a range from the lowest to the highest \
+             coordinate, found '30..20'"
+        );
+        assert_eq!(
+            error("target area: x=20..30"),
+            "day 17, line 1, column 1: expected a target area, e.g. 'target area: x=20..30, \
+             y=-10..-5', found 'target area: x=20..30'"
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use crate::error::{parse_lines, ParseError};
+use crate::solution::Solution;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Pairs nested inside this many pairs explode when a number is reduced
+pub const MAX_DEPTH: usize = 4;
+
+/// The deepest a parsed number can be nested, which keeps parsing from overflowing the stack
+pub const MAX_NESTING: usize = 64;
+
+/// A snailfish number: a regular number, or a pair of snailfish numbers
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+impl Number {
+    pub fn pair(left: Number, right: Number) -> Self {
+        Number::Pair(Box::new(left), Box::new(right))
+    }
+
+    /// Explodes and splits until neither applies. Each step explodes the leftmost pair nested
+    /// too deeply, or if there's none, splits the leftmost regular number of 10 or more.
+    pub fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    /// Explodes the leftmost pair nested inside [MAX_DEPTH] pairs, returning the pair's values
+    /// that are still to be added to the nearest regular numbers to its left and right
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let (left, right) = match self {
+            Number::Regular(_) => return None,
+            Number::Pair(left, right) => (left, right),
+        };
+
+        if depth >= MAX_DEPTH {
+            if let (Number::Regular(l), Number::Regular(r)) = (&**left, &**right) {
+                let carry = (Some(*l), Some(*r));
+                *self = Number::Regular(0);
+                return Some(carry);
+            }
+        }
+
+        if let Some((carry_left, carry_right)) = left.explode(depth + 1) {
+            if let Some(value) = carry_right {
+                right.add_to_leftmost(value);
+            }
+            return Some((carry_left, None));
+        }
+        if let Some((carry_left, carry_right)) = right.explode(depth + 1) {
+            if let Some(value) = carry_left {
+                left.add_to_rightmost(value);
+            }
+            return Some((None, carry_right));
+        }
+        None
+    }
+
+    fn add_to_leftmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(left, _) => left.add_to_leftmost(value),
+        }
+    }
+
+    fn add_to_rightmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(_, right) => right.add_to_rightmost(value),
+        }
+    }
+
+    /// Splits the leftmost regular number of 10 or more into a pair of its halves, rounding the
+    /// left down and the right up. Returns whether a number was split.
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(n) if *n >= 10 => {
+                let n = *n;
+                *self = Number::pair(Number::Regular(n / 2), Number::Regular(n.div_ceil(2)));
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+
+    /// Whether the number is nested no deeper than [MAX_DEPTH] pairs and has only single digit
+    /// regular numbers, so that neither exploding nor splitting applies
+    pub fn is_reduced(&self) -> bool {
+        self.is_reduced_at(0)
+    }
+
+    fn is_reduced_at(&self, depth: usize) -> bool {
+        match self {
+            Number::Regular(n) => *n < 10,
+            Number::Pair(left, right) => {
+                depth < MAX_DEPTH && left.is_reduced_at(depth + 1) && right.is_reduced_at(depth + 1)
+            }
+        }
+    }
+
+    /// Three times the magnitude of the left of a pair, plus twice the right
+    pub fn magnitude(&self) -> u64 {
+        match self {
+            Number::Regular(n) => *n as u64,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    /// Parses a number from its position in a line, advancing the position past it
+    fn parse(line: &str, position: &mut usize, depth: usize) -> Result<Self, ParseError> {
+        let error = |position: usize, expected: &str| {
+            let end = line[position..]
+                .chars()
+                .next()
+                .map_or(position, |c| position + c.len_utf8());
+            ParseError::new(Day18::DAY, line, &line[position..end], expected)
+        };
+        let expect = |position: &mut usize, c: char| {
+            if line[*position..].starts_with(c) {
+                *position += 1;
+                Ok(())
+            } else {
+                Err(error(*position, &format!("'{}'", c)))
+            }
+        };
+
+        match line[*position..].chars().next() {
+            Some(c @ '0'..='9') => {
+                *position += 1;
+                Ok(Number::Regular(c.to_digit(10).unwrap()))
+            }
+            Some('[') if depth == MAX_NESTING => Err(error(
+                *position,
+                &format!("a number nested inside at most {} pairs", MAX_NESTING),
+            )),
+            Some('[') => {
+                *position += 1;
+                let left = Self::parse(line, position, depth + 1)?;
+                expect(position, ',')?;
+                let right = Self::parse(line, position, depth + 1)?;
+                expect(position, ']')?;
+                Ok(Number::pair(left, right))
+            }
+            _ => Err(error(*position, "a digit or a pair")),
+        }
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    /// Parses a number, such as `[[1,2],3]`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut position = 0;
+        let number = Self::parse(s, &mut position, 0)?;
+        if position < s.len() {
+            return Err(ParseError::new(
+                Day18::DAY,
+                s,
+                &s[position..],
+                "the end of the number",
+            ));
+        }
+        Ok(number)
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Regular(n) => write!(f, "{}", n),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    /// Pairs the numbers, then reduces the pair
+    fn add(self, other: Self) -> Self::Output {
+        let mut sum = Number::pair(self, other);
+        sum.reduce();
+        sum
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+    type Input = Vec<Number>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let numbers = parse_lines(input, |l| {
+            let number = Number::from_str(l)?;
+            if !number.is_reduced() {
+                return Err(ParseError::new(
+                    Self::DAY,
+                    l,
+                    l,
+                    "a reduced snailfish number",
+                ));
+            }
+            Ok(number)
+        })?;
+
+        if numbers.len() < 2 {
+            return Err(
+                ParseError::new(Self::DAY, "", "", "at least two snailfish numbers")
+                    .on_line(input.lines().count() + 1),
+            );
+        }
+
+        Ok(numbers)
+    }
+
+    fn part_1(numbers: &Self::Input) -> Self::Part1 {
+        let sum = numbers.iter().cloned().reduce(|sum, n| sum + n).unwrap();
+        sum.magnitude()
+    }
+
+    fn part_2(numbers: &Self::Input) -> Self::Part2 {
+        let mut largest = 0;
+        for (i, a) in numbers.iter().enumerate() {
+            for (j, b) in numbers.iter().enumerate() {
+                if i != j {
+                    largest = largest.max((a.clone() + b.clone()).magnitude());
+                }
+            }
+        }
+        largest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+    fn number(s: &str) -> Number {
+        Number::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays_numbers() {
+        let n = number("[[1,2],3]");
+        assert_eq!(
+            n,
+            Number::pair(
+                Number::pair(Number::Regular(1), Number::Regular(2)),
+                Number::Regular(3)
+            )
+        );
+        assert_eq!(n.to_string(), "[[1,2],3]");
+    }
+
+    #[test]
+    fn explodes_the_leftmost_deep_pair() {
+        for (before, after) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+        ] {
+            let mut n = number(before);
+            assert!(n.explode(0).is_some(), "{}", before);
+            assert_eq!(n.to_string(), after);
+        }
+    }
+
+    #[test]
+    fn adds_and_reduces() {
+        let sum = number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]");
+        assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+
+        let sum = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]
+[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]
+[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]"
+            .lines()
+            .map(number)
+            .reduce(|sum, n| sum + n)
+            .unwrap();
+        assert_eq!(
+            sum.to_string(),
+            "[[[[6,7],[6,7]],[[7,7],[0,7]]],[[[8,7],[7,7]],[[8,8],[8,0]]]]"
+        );
+    }
+
+    #[test]
+    fn finds_magnitudes() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
+            3488
+        );
+    }
+
+    #[test]
+    fn part_1_finds_the_magnitude_of_the_sum() {
+        let numbers = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part_1(&numbers), 4140);
+    }
+
+    #[test]
+    fn part_2_finds_the_largest_magnitude_of_two_numbers() {
+        let numbers = Day18::parse(EXAMPLE).unwrap();
+        assert_eq!(Day18::part_2(&numbers), 3993);
+    }
+
+    #[test]
+    fn rejects_numbers_that_are_not_reduced() {
+        let error = |input| Day18::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("[1,2]\n[[[[[1,2],3],4],5],6]"),
+            "day 18, line 2, column 1: expected a reduced snailfish number, found \
+             '[[[[[1,2],3],4],5],6]'"
+        );
+        assert_eq!(
+            error("[1,2]\n[1;2]"),
+            "day 18, line 2, column 3: expected ',', found ';'"
+        );
+        assert_eq!(
+            error("[1,2]"),
+            "day 18, line 2, column 1: expected at least two snailfish numbers, found nothing"
+        );
+    }
+}
@@ -0,0 +1,526 @@
+use crate::error::{parse_token, ParseError};
+use crate::solution::{Checked, Solution};
+use std::collections::{HashMap, HashSet};
+
+/// An `(x, y, z)` position, relative to a scanner or, once aligned, to scanner 0
+pub type Coord = (i32, i32, i32);
+
+/// The furthest a scanner detects beacons along each axis
+pub const RANGE: i32 = 1000;
+
+/// The number of beacons two scanners must both detect to be aligned
+pub const OVERLAP: usize = 12;
+
+pub fn manhattan_distance(a: Coord, b: Coord) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs() + (a.2 - b.2).abs()
+}
+
+/// One of the 24 ways a scanner can be facing. Each axis of a rotated coordinate is one of the
+/// original axes, possibly negated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    /// Every rotation, starting with the one that leaves coordinates as they are
+    pub fn all() -> Vec<Rotation> {
+        let permutations = [
+            [0, 1, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [0, 2, 1],
+            [2, 1, 0],
+            [1, 0, 2],
+        ];
+        let mut rotations = vec![];
+        for (i, axes) in permutations.iter().enumerate() {
+            for signs in [
+                [1, 1, 1],
+                [1, -1, -1],
+                [-1, 1, -1],
+                [-1, -1, 1],
+                [-1, -1, -1],
+                [-1, 1, 1],
+                [1, -1, 1],
+                [1, 1, -1],
+            ] {
+                // odd permutations need an odd number of negated axes, or they'd be reflections
+                let negated = signs.iter().filter(|&&s| s < 0).count();
+                if (i < 3) == (negated % 2 == 0) {
+                    rotations.push(Rotation { axes: *axes, signs });
+                }
+            }
+        }
+        rotations
+    }
+
+    pub fn apply(&self, c: Coord) -> Coord {
+        let c = [c.0, c.1, c.2];
+        (
+            c[self.axes[0]] * self.signs[0],
+            c[self.axes[1]] * self.signs[1],
+            c[self.axes[2]] * self.signs[2],
+        )
+    }
+}
+
+/// The beacons a scanner detects, relative to the scanner
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    pub beacons: Vec<Coord>,
+    /// The squared distance between each pair of beacons, sorted. These don't depend on the
+    /// scanner's position or rotation, so scanners that share too few can't overlap.
+    distances: Vec<i64>,
+}
+
+impl Scanner {
+    pub fn new(beacons: Vec<Coord>) -> Self {
+        let mut distances = vec![];
+        for (i, a) in beacons.iter().enumerate() {
+            for b in &beacons[i + 1..] {
+                let d = |a: i32, b: i32| (a as i64 - b as i64).pow(2);
+                distances.push(d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2));
+            }
+        }
+        distances.sort_unstable();
+        Self { beacons, distances }
+    }
+
+    /// Whether enough beacons are the same distances apart that the scanners could overlap
+    fn might_overlap(&self, other: &Scanner) -> bool {
+        let (mut i, mut j, mut shared) = (0, 0, 0);
+        while i < self.distances.len() && j < other.distances.len() {
+            match self.distances[i].cmp(&other.distances[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    shared += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        shared >= OVERLAP * (OVERLAP - 1) / 2
+    }
+
+    /// Finds the rotation and position of this scanner relative to beacons that are already
+    /// placed, if at least [OVERLAP] of its beacons match them
+    fn align(&self, placed: &[Coord]) -> Option<(Rotation, Coord)> {
+        for rotation in Rotation::all() {
+            let mut offsets = HashMap::new();
+            for b in &self.beacons {
+                let (x, y, z) = rotation.apply(*b);
+                for a in placed {
+                    let offset = (a.0 - x, a.1 - y, a.2 - z);
+                    let count = offsets.entry(offset).or_insert(0);
+                    *count += 1;
+                    if *count == OVERLAP {
+                        return Some((rotation, offset));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Every beacon and scanner, relative to scanner 0
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub beacons: HashSet<Coord>,
+    pub scanners: Vec<Coord>,
+}
+
+impl Map {
+    /// Aligns each scanner with one already placed, starting from scanner 0, or returns `None`
+    /// if some scanner doesn't overlap any other
+    pub fn merge(scanners: &[Scanner]) -> Option<Self> {
+        let mut placed: Vec<Option<(Vec<Coord>, Coord)>> = vec![None; scanners.len()];
+        placed[0] = Some((scanners[0].beacons.clone(), (0, 0, 0)));
+        let mut queue = vec![0];
+        while let Some(i) = queue.pop() {
+            for j in 0..scanners.len() {
+                if placed[j].is_some() || !scanners[i].might_overlap(&scanners[j]) {
+                    continue;
+                }
+
+                let beacons = &placed[i].as_ref().unwrap().0;
+                if let Some((rotation, (x, y, z))) = scanners[j].align(beacons) {
+                    let beacons = scanners[j]
+                        .beacons
+                        .iter()
+                        .map(|b| {
+                            let b = rotation.apply(*b);
+                            (b.0 + x, b.1 + y, b.2 + z)
+                        })
+                        .collect();
+                    placed[j] = Some((beacons, (x, y, z)));
+                    queue.push(j);
+                }
+            }
+        }
+
+        let placed = placed.into_iter().collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            beacons: placed.iter().flat_map(|(b, _)| b).copied().collect(),
+            scanners: placed.iter().map(|(_, s)| *s).collect(),
+        })
+    }
+}
+
+/// The answer to both parts when some scanner doesn't overlap the others
+const UNALIGNED: &str = "unaligned";
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+    type Input = Option<Map>;
+    type Part1 = Checked<usize>;
+    type Part2 = Checked<i32>;
+
+    /// Parses each scanner's report and aligns the scanners into a map, or `None` if some scanner
+    /// doesn't overlap the others
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut scanners = vec![];
+        let mut beacons = None;
+        let expected_coordinate = format!("a coordinate from -{} to {}", RANGE, RANGE);
+        for (i, l) in input.lines().enumerate() {
+            let error = |token, expected: &str| {
+                ParseError::new(Self::DAY, l, token, expected).on_line(i + 1)
+            };
+            let beacons = match (&mut beacons, l) {
+                (None, l) => {
+                    let header = format!("--- scanner {} ---", scanners.len());
+                    if l != header {
+                        return Err(error(l, &format!("'{}'", header)));
+                    }
+                    beacons = Some(vec![]);
+                    continue;
+                }
+                (Some(_), "") => {
+                    scanners.push(Scanner::new(beacons.take().unwrap()));
+                    continue;
+                }
+                (Some(beacons), _) => beacons,
+            };
+
+            let coordinates: Vec<_> = l.split(',').collect();
+            if coordinates.len() != 3 {
+                return Err(error(l, "a beacon, e.g. '-618,-824,-621'"));
+            }
+            let coordinate = |c| match parse_token(Self::DAY, l, c, &expected_coordinate) {
+                Ok(n) if (-RANGE..=RANGE).contains(&n) => Ok(n),
+                _ => Err(error(c, &expected_coordinate)),
+            };
+            beacons.push((
+                coordinate(coordinates[0])?,
+                coordinate(coordinates[1])?,
+                coordinate(coordinates[2])?,
+            ));
+        }
+        scanners.extend(beacons.map(Scanner::new));
+
+        if scanners.is_empty() {
+            let end = input.lines().count() + 1;
+            return Err(ParseError::new(Self::DAY, "", "", "a scanner").on_line(end));
+        }
+        Ok(Map::merge(&scanners))
+    }
+
+    fn part_1(map: &Self::Input) -> Self::Part1 {
+        match map {
+            Some(map) => Checked::Answer(map.beacons.len()),
+            None => Checked::NoAnswer(UNALIGNED),
+        }
+    }
+
+    fn part_2(map: &Self::Input) -> Self::Part2 {
+        let scanners = match map {
+            Some(map) => &map.scanners,
+            None => return Checked::NoAnswer(UNALIGNED),
+        };
+        let furthest = scanners
+            .iter()
+            .flat_map(|a| scanners.iter().map(move |b| manhattan_distance(*a, *b)))
+            .max()
+            .unwrap();
+        Checked::Answer(furthest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+    /// Beacons spread out so that no two pairs are the same distance apart by chance
+    fn beacons(count: i32) -> Vec<Coord> {
+        (1..=count)
+            .map(|i| {
+                (
+                    i * 37 % 401 - 200,
+                    i * i * 13 % 397 - 190,
+                    i * i * i % 389 - 180,
+                )
+            })
+            .collect()
+    }
+
+    /// Writes the reports of scanners at each position and rotation, each detecting the beacons
+    /// with the given indices
+    fn reports(beacons: &[Coord], scanners: &[(Coord, usize, &[usize])]) -> String {
+        let rotations = Rotation::all();
+        scanners
+            .iter()
+            .enumerate()
+            .map(|(i, ((x, y, z), rotation, seen))| {
+                let mut report = format!("--- scanner {} ---", i);
+                for &b in *seen {
+                    let (bx, by, bz) = beacons[b];
+                    let (rx, ry, rz) = rotations[*rotation].apply((bx - x, by - y, bz - z));
+                    report.push_str(&format!("\n{},{},{}", rx, ry, rz));
+                }
+                report
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    #[test]
+    fn part_1_counts_beacons_across_all_scanners() {
+        let map = Day19::parse(EXAMPLE).unwrap();
+        assert_eq!(Day19::part_1(&map), Checked::Answer(79));
+    }
+
+    #[test]
+    fn part_2_finds_the_furthest_apart_scanners() {
+        let map = Day19::parse(EXAMPLE).unwrap();
+        let aligned = map.as_ref().unwrap();
+        assert_eq!(aligned.scanners[2], (1105, -1205, 1229));
+        assert_eq!(aligned.scanners[3], (-92, -2380, -20));
+        assert_eq!(Day19::part_2(&map), Checked::Answer(3621));
+    }
+
+    #[test]
+    fn has_24_distinct_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations[0].apply((1, 2, 3)), (1, 2, 3));
+        let rotated: HashSet<_> = rotations.iter().map(|r| r.apply((1, 2, 3))).collect();
+        assert_eq!(rotated.len(), 24);
+        // a rotation keeps a right-handed set of axes right-handed
+        for r in rotations {
+            let (x, y, z) = (r.apply((1, 0, 0)), r.apply((0, 1, 0)), r.apply((0, 0, 1)));
+            let cross = (
+                x.1 * y.2 - x.2 * y.1,
+                x.2 * y.0 - x.0 * y.2,
+                x.0 * y.1 - x.1 * y.0,
+            );
+            assert_eq!(cross, z, "{:?}", r);
+        }
+    }
+
+    #[test]
+    fn aligns_scanners_that_share_12_beacons() {
+        let beacons = beacons(40);
+        let all: Vec<_> = (0..40).collect();
+        let input = reports(
+            &beacons,
+            &[
+                ((0, 0, 0), 0, &all[..20]),
+                ((68, -646, -43), 7, &all[8..30]),
+                ((705, -605, 629), 19, &all[18..40]),
+            ],
+        );
+
+        let map = Day19::parse(&input).unwrap();
+        let aligned = map.as_ref().unwrap();
+        assert_eq!(
+            aligned.scanners,
+            [(0, 0, 0), (68, -646, -43), (705, -605, 629)]
+        );
+        assert_eq!(Day19::part_1(&map), Checked::Answer(40));
+        assert_eq!(Day19::part_2(&map), Checked::Answer(705 + 605 + 629));
+    }
+
+    #[test]
+    fn reports_scanners_that_dont_overlap() {
+        let beacons = beacons(40);
+        let all: Vec<_> = (0..40).collect();
+        let input = reports(
+            &beacons,
+            &[((0, 0, 0), 0, &all[..20]), ((10, 20, 30), 3, &all[8..30])],
+        );
+        let map = Day19::parse(&input).unwrap();
+        assert_eq!(Day19::part_1(&map), Checked::Answer(30));
+
+        let input = reports(
+            &beacons,
+            &[
+                ((0, 0, 0), 0, &all[..20]),
+                ((10, 20, 30), 3, &all[8..30]),
+                ((0, 0, 0), 5, &all[29..]),
+            ],
+        );
+        let map = Day19::parse(&input).unwrap();
+        assert_eq!(Day19::part_1(&map), Checked::NoAnswer(UNALIGNED));
+        assert_eq!(Day19::part_2(&map), Checked::NoAnswer(UNALIGNED));
+        assert_eq!(Day19::part_1(&map).to_string(), "unaligned");
+    }
+
+    #[test]
+    fn rejects_malformed_reports() {
+        let error = |input| Day19::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("--- scanner 1 ---"),
+            "day 19, line 1, column 1: expected '--- scanner 0 ---', found '--- scanner 1 ---'"
+        );
+        assert_eq!(
+            error("--- scanner 0 ---\n1,2,1001"),
+            "day 19, line 2, column 5: expected a coordinate from -1000 to 1000, found '1001'"
+        );
+        assert_eq!(
+            error("--- scanner 0 ---\n1,2"),
+            "day 19, line 2, column 1: expected a beacon, e.g. '-618,-824,-621', found '1,2'"
+        );
+    }
+}
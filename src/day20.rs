@@ -0,0 +1,257 @@
+use crate::error::ParseError;
+use crate::grid;
+use crate::solution::{Checked, Solution};
+use std::fmt::{Display, Formatter};
+
+/// The number of times the image is enhanced for part 1
+pub const PART_1_STEPS: usize = 2;
+/// The number of times the image is enhanced for part 2
+pub const PART_2_STEPS: usize = 50;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Pixel {
+    Light,
+    Dark,
+}
+
+impl Display for Pixel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pixel::Light => write!(f, "#"),
+            Pixel::Dark => write!(f, "."),
+        }
+    }
+}
+
+impl Pixel {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(Pixel::Light),
+            '.' => Some(Pixel::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// The image enhancement algorithm: the output pixel for each 3x3 square of input pixels, read
+/// row by row as a 9 bit number with light pixels as 1
+#[derive(Debug, Clone)]
+pub struct Algorithm([Pixel; 512]);
+
+/// An image with an infinite background. Every pixel beyond the image's bounds is the same,
+/// and as that includes every 3x3 square of them, the background can flip between light and
+/// dark on each step.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pixels: grid::Grid<Pixel>,
+    background: Pixel,
+}
+
+impl Display for Image {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pixels)
+    }
+}
+
+impl Image {
+    pub fn new(pixels: grid::Grid<Pixel>) -> Self {
+        Self {
+            pixels,
+            background: Pixel::Dark,
+        }
+    }
+
+    /// The pixel at a position, which may be out of bounds, relative to the top left
+    fn pixel(&self, x: isize, y: isize) -> Pixel {
+        if x < 0 || y < 0 {
+            return self.background;
+        }
+        *self
+            .pixels
+            .get((x as usize, y as usize))
+            .unwrap_or(&self.background)
+    }
+
+    /// Applies the algorithm to every pixel at once. The image grows by a pixel on each side, as
+    /// those are the furthest pixels affected by the image rather than only the background.
+    pub fn enhance(&self, algorithm: &Algorithm) -> Self {
+        let (width, height) = (self.pixels.width() + 2, self.pixels.height() + 2);
+        let pixels = grid::Grid::from_fn(width, height, |(x, y)| {
+            // (x, y) of the enhanced image is (x - 1, y - 1) of this one
+            let (x, y) = (x as isize - 1, y as isize - 1);
+            let mut index = 0;
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    index = index << 1 | (self.pixel(x + dx, y + dy) == Pixel::Light) as usize;
+                }
+            }
+            algorithm.0[index]
+        });
+        let background = match self.background {
+            Pixel::Dark => algorithm.0[0],
+            Pixel::Light => algorithm.0[511],
+        };
+
+        Self { pixels, background }
+    }
+
+    pub fn lit(&self) -> Checked<usize> {
+        match self.background {
+            Pixel::Light => Checked::NoAnswer(INFINITE),
+            Pixel::Dark => Checked::Answer(
+                self.pixels
+                    .iter()
+                    .filter(|(_, p)| **p == Pixel::Light)
+                    .count(),
+            ),
+        }
+    }
+
+    pub fn pixels(&self) -> &grid::Grid<Pixel> {
+        &self.pixels
+    }
+}
+
+/// The answer when the background is lit, as then so are infinitely many pixels
+const INFINITE: &str = "infinitely many lit";
+
+pub struct Day20;
+
+impl Day20 {
+    fn lit_after(input: &(Algorithm, Image), steps: usize) -> Checked<usize> {
+        let (algorithm, image) = input;
+        let mut image = image.clone();
+        for _ in 0..steps {
+            image = image.enhance(algorithm);
+        }
+        image.lit()
+    }
+}
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+    type Input = (Algorithm, Image);
+    type Part1 = Checked<usize>;
+    type Part2 = Checked<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = input.lines();
+        let line = lines.next().unwrap_or_default();
+        let mut algorithm = [Pixel::Dark; 512];
+        for (i, (x, c)) in line.char_indices().enumerate() {
+            let pixel = Pixel::from_char(c).ok_or_else(|| {
+                ParseError::new(Self::DAY, line, &line[x..x + c.len_utf8()], "'#' or '.'")
+            })?;
+            if i == algorithm.len() {
+                return Err(ParseError::new(
+                    Self::DAY,
+                    line,
+                    &line[x..],
+                    "the end of the algorithm",
+                ));
+            }
+            algorithm[i] = pixel;
+        }
+        if line.len() < algorithm.len() {
+            return Err(ParseError::new(
+                Self::DAY,
+                line,
+                &line[line.len()..],
+                "an algorithm of 512 pixels",
+            ));
+        }
+
+        if let Some(l) = lines.next().filter(|l| !l.is_empty()) {
+            return Err(ParseError::new(Self::DAY, l, l, "an empty line").on_line(2));
+        }
+
+        let image = input.lines().skip(2).collect::<Vec<_>>().join("\n");
+        let pixels =
+            grid::Grid::parse(Self::DAY, &image, "'#' or '.'", Pixel::from_char).map_err(|e| {
+                let line = e.line + 2;
+                e.on_line(line)
+            })?;
+
+        Ok((Algorithm(algorithm), Image::new(pixels)))
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        Self::lit_after(input, PART_1_STEPS)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        Self::lit_after(input, PART_2_STEPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+    #[test]
+    fn enhances_and_grows_the_image() {
+        let (algorithm, image) = Day20::parse(EXAMPLE).unwrap();
+        let image = image.enhance(&algorithm);
+        assert_eq!(
+            image.to_string(),
+            "\
+.##.##.
+#..#.#.
+##.#..#
+####..#
+.#..##.
+..##..#
+...#.#.
+"
+        );
+        assert_eq!(image.lit(), Checked::Answer(24));
+    }
+
+    #[test]
+    fn part_1_counts_lit_pixels_after_2_steps() {
+        let input = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part_1(&input), Checked::Answer(35));
+    }
+
+    #[test]
+    fn part_2_counts_lit_pixels_after_50_steps() {
+        let input = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(Day20::part_2(&input), Checked::Answer(3351));
+    }
+
+    #[test]
+    fn the_background_flips_when_dark_squares_light_up() {
+        let mut algorithm = [Pixel::Dark; 512];
+        algorithm[0] = Pixel::Light;
+        let algorithm = Algorithm(algorithm);
+        let image = Image::new(grid::Grid::new(1, 1, Pixel::Dark));
+
+        let image = image.enhance(&algorithm);
+        assert_eq!(image.lit().to_string(), "infinitely many lit");
+        assert_eq!(image.to_string(), "###\n###\n###\n");
+        let image = image.enhance(&algorithm);
+        assert_eq!(image.lit(), Checked::Answer(0));
+    }
+
+    #[test]
+    fn rejects_short_algorithms() {
+        assert_eq!(
+            Day20::parse("..#\n\n#.").unwrap_err().to_string(),
+            "day 20, line 1, column 4: expected an algorithm of 512 pixels, found nothing"
+        );
+        let input = EXAMPLE.replace("\n##..#\n", "\n##x.#\n");
+        assert_eq!(
+            Day20::parse(&input).unwrap_err().to_string(),
+            "day 20, line 5, column 3: expected '#' or '.', found 'x'"
+        );
+    }
+}
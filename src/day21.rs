@@ -0,0 +1,278 @@
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+/// The score that wins a game with the deterministic die
+pub const PRACTICE_WINNING_SCORE: u32 = 1000;
+
+/// The score that wins a game with the Dirac die
+pub const DIRAC_WINNING_SCORE: u32 = 21;
+
+/// Each total of three rolls of the Dirac die, with the number of the 27 universes the rolls
+/// split into that roll it
+const DIRAC_ROLLS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Player {
+    /// The space the player is on, from 1 to 10
+    pub position: u32,
+    pub score: u32,
+}
+
+impl Player {
+    pub fn new(position: u32) -> Self {
+        Self { position, score: 0 }
+    }
+
+    /// Moves the player forward around the track, and adds the space they land on to their score
+    pub fn advance(self, spaces: u32) -> Self {
+        let position = (self.position + spaces - 1) % 10 + 1;
+        Self {
+            position,
+            score: self.score + position,
+        }
+    }
+}
+
+/// A die that rolls 1, 2, 3 and so on up to 100, then starts again from 1
+#[derive(Debug, Clone, Default)]
+pub struct DeterministicDie {
+    rolls: u32,
+}
+
+impl DeterministicDie {
+    pub fn rolls(&self) -> u32 {
+        self.rolls
+    }
+}
+
+impl Iterator for DeterministicDie {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rolls += 1;
+        Some((self.rolls - 1) % 100 + 1)
+    }
+}
+
+/// The state of a game: both players, and whose turn it is
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Game {
+    pub players: [Player; 2],
+    pub turn: usize,
+}
+
+impl Game {
+    pub fn new(positions: [u32; 2]) -> Self {
+        Self {
+            players: [Player::new(positions[0]), Player::new(positions[1])],
+            turn: 0,
+        }
+    }
+
+    /// The game after the player whose turn it is moves a number of spaces
+    pub fn play(&self, spaces: u32) -> Self {
+        let mut players = self.players;
+        players[self.turn] = players[self.turn].advance(spaces);
+        Self {
+            players,
+            turn: 1 - self.turn,
+        }
+    }
+}
+
+/// Counts the universes each player wins in from each game state. Universes that reach the same
+/// state play out the same way from then on, so like the fish of the same age in day 6, or the
+/// same pairs of a polymer in day 14, each state is counted once and weighted by the number of
+/// universes that reach it.
+#[derive(Debug)]
+pub struct Multiverse {
+    winning_score: u32,
+    wins: HashMap<Game, [u64; 2]>,
+}
+
+impl Multiverse {
+    pub fn new(winning_score: u32) -> Self {
+        Self {
+            winning_score,
+            wins: HashMap::new(),
+        }
+    }
+
+    /// The number of universes each player wins in, starting from the game
+    pub fn wins(&mut self, game: Game) -> [u64; 2] {
+        if let Some(wins) = self.wins.get(&game) {
+            return *wins;
+        }
+
+        let mut wins = [0, 0];
+        for (total, universes) in DIRAC_ROLLS {
+            let next = game.play(total);
+            if next.players[game.turn].score >= self.winning_score {
+                wins[game.turn] += universes;
+            } else {
+                let next_wins = self.wins(next);
+                wins[0] += universes * next_wins[0];
+                wins[1] += universes * next_wins[1];
+            }
+        }
+
+        self.wins.insert(game, wins);
+        wins
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+    type Input = [u32; 2];
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut player = 0;
+        let positions = parse_lines(input, |l| {
+            player += 1;
+            let prefix = format!("Player {} starting position: ", player);
+            let position = l.strip_prefix(&prefix).ok_or_else(|| {
+                ParseError::new(
+                    Self::DAY,
+                    l,
+                    l,
+                    format!("a starting position, e.g. '{}4'", prefix),
+                )
+            })?;
+            match parse_token(Self::DAY, l, position, "a position from 1 to 10")? {
+                p @ 1..=10 => Ok(p),
+                _ => Err(ParseError::new(
+                    Self::DAY,
+                    l,
+                    position,
+                    "a position from 1 to 10",
+                )),
+            }
+        })?;
+
+        match positions[..] {
+            [a, b] => Ok([a, b]),
+            _ => {
+                let line = positions.len().min(2) + 1;
+                let l = input.lines().nth(2).unwrap_or_default();
+                Err(ParseError::new(Self::DAY, l, l, "two players").on_line(line))
+            }
+        }
+    }
+
+    fn part_1(positions: &Self::Input) -> Self::Part1 {
+        let mut die = DeterministicDie::default();
+        let mut game = Game::new(*positions);
+        loop {
+            let spaces = die.by_ref().take(3).sum();
+            let turn = game.turn;
+            game = game.play(spaces);
+            if game.players[turn].score >= PRACTICE_WINNING_SCORE {
+                return game.players[1 - turn].score * die.rolls();
+            }
+        }
+    }
+
+    fn part_2(positions: &Self::Input) -> Self::Part2 {
+        let wins = Multiverse::new(DIRAC_WINNING_SCORE).wins(Game::new(*positions));
+        wins[0].max(wins[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "\
+Player 1 starting position: 4
+Player 2 starting position: 8";
+
+    #[test]
+    fn dirac_rolls_cover_all_27_universes() {
+        let universes: u64 = DIRAC_ROLLS.iter().map(|(_, u)| u).sum();
+        assert_eq!(universes, 27);
+    }
+
+    #[test]
+    fn players_wrap_around_the_track() {
+        let player = Player::new(7).advance(2 + 2 + 1);
+        assert_eq!(
+            player,
+            Player {
+                position: 2,
+                score: 2
+            }
+        );
+    }
+
+    #[test]
+    fn part_1_plays_with_the_deterministic_die() {
+        let positions = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(Day21::part_1(&positions), 739785);
+    }
+
+    #[test]
+    fn part_2_counts_universes_won() {
+        let positions = Day21::parse(EXAMPLE).unwrap();
+        let wins = Multiverse::new(DIRAC_WINNING_SCORE).wins(Game::new(positions));
+        assert_eq!(wins, [444356092776315, 341960390180808]);
+        assert_eq!(Day21::part_2(&positions), 444356092776315);
+    }
+
+    #[test]
+    fn rejects_other_numbers_of_players() {
+        let error = |input| Day21::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("Player 1 starting position: 4"),
+            "day 21, line 2, column 1: expected two players, found nothing"
+        );
+        assert_eq!(
+            error("Player 1 starting position: 11"),
+            "day 21, line 1, column 29: expected a position from 1 to 10, found '11'"
+        );
+        assert_eq!(
+            error("Player 2 starting position: 4"),
+            "day 21, line 1, column 1: expected a starting position, e.g. 'Player 1 starting \
+             position: 4', found 'Player 2 starting position: 4'"
+        );
+    }
+
+    /// Splits the universe on every roll of the die, as the puzzle describes
+    fn split(game: Game, winning_score: u32) -> [u64; 2] {
+        let mut wins = [0, 0];
+        for a in 1..=3 {
+            for b in 1..=3 {
+                for c in 1..=3 {
+                    let next = game.play(a + b + c);
+                    if next.players[game.turn].score >= winning_score {
+                        wins[game.turn] += 1;
+                    } else {
+                        let next_wins = split(next, winning_score);
+                        wins[0] += next_wins[0];
+                        wins[1] += next_wins[1];
+                    }
+                }
+            }
+        }
+        wins
+    }
+
+    proptest! {
+        #[test]
+        fn counting_states_matches_splitting_every_universe(
+            positions in [1..=10u32, 1..=10u32],
+            winning_score in 1..=8u32,
+        ) {
+            let game = Game::new(positions);
+            prop_assert_eq!(
+                Multiverse::new(winning_score).wins(game),
+                split(game, winning_score)
+            );
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use crate::error::{parse_lines, parse_token, ParseError};
+use crate::solution::{Checked, Solution};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// The furthest coordinate of the initialization region of part 1
+pub const INITIALIZATION: i64 = 50;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Cuboid {
+    pub x: RangeInclusive<i64>,
+    pub y: RangeInclusive<i64>,
+    pub z: RangeInclusive<i64>,
+}
+
+impl Cuboid {
+    /// The cuboid of every cube within a distance of the origin along each axis
+    pub fn around_origin(distance: i64) -> Self {
+        Self {
+            x: -distance..=distance,
+            y: -distance..=distance,
+            z: -distance..=distance,
+        }
+    }
+
+    /// The number of cubes in the cuboid, or `None` if there are too many to count
+    pub fn volume(&self) -> Option<i128> {
+        [&self.x, &self.y, &self.z]
+            .iter()
+            .map(|r| *r.end() as i128 - *r.start() as i128 + 1)
+            .try_fold(1_i128, |volume, length| volume.checked_mul(length))
+    }
+
+    /// The cubes in both cuboids, if there are any
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let overlap = |a: &RangeInclusive<i64>, b: &RangeInclusive<i64>| {
+            let range = *a.start().max(b.start())..=*a.end().min(b.end());
+            Some(range).filter(|r| !r.is_empty())
+        };
+        Some(Cuboid {
+            x: overlap(&self.x, &other.x)?,
+            y: overlap(&self.y, &other.y)?,
+            z: overlap(&self.z, &other.z)?,
+        })
+    }
+
+    /// Parses a cuboid from part of a line, so that errors point into the whole line
+    fn parse(line: &str, s: &str) -> Result<Self, ParseError> {
+        let mut axes = s.splitn(3, ',');
+        let mut range = |axis: &str| {
+            let r = axes.next().unwrap_or(&s[s.len()..]);
+            let (start, end) = r
+                .strip_prefix(axis)
+                .and_then(|r| r.strip_prefix('='))
+                .and_then(|r| r.split_once(".."))
+                .ok_or_else(|| {
+                    ParseError::new(
+                        Day22::DAY,
+                        line,
+                        r,
+                        format!("a range of {}, e.g. '{}=10..12'", axis, axis),
+                    )
+                })?;
+            let coordinate = |c| parse_token::<i64>(Day22::DAY, line, c, "a coordinate");
+            let (start, end) = (coordinate(start)?, coordinate(end)?);
+            if start > end {
+                return Err(ParseError::new(
+                    Day22::DAY,
+                    line,
+                    r,
+                    "a range from the lowest to the highest coordinate",
+                ));
+            }
+            Ok(start..=end)
+        };
+
+        Ok(Self {
+            x: range("x")?,
+            y: range("y")?,
+            z: range("z")?,
+        })
+    }
+}
+
+impl FromStr for Cuboid {
+    type Err = ParseError;
+
+    /// Parses a cuboid, such as `x=10..12,y=10..12,z=10..12`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, s)
+    }
+}
+
+impl Display for Cuboid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Cuboid { x, y, z } = self;
+        write!(
+            f,
+            "x={}..{},y={}..{},z={}..{}",
+            x.start(),
+            x.end(),
+            y.start(),
+            y.end(),
+            z.start(),
+            z.end()
+        )
+    }
+}
+
+/// A reboot step, which turns every cube in the cuboid on or off
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+impl FromStr for Step {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (on, cuboid) = s.split_once(' ').unwrap_or((s, &s[s.len()..]));
+        let on = match on {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::new(Day22::DAY, s, on, "'on' or 'off'")),
+        };
+        Ok(Self {
+            on,
+            cuboid: Cuboid::parse(s, cuboid)?,
+        })
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let on = if self.on { "on" } else { "off" };
+        write!(f, "{} {}", on, self.cuboid)
+    }
+}
+
+/// The cubes that are on, as cuboids that are each counted a number of times. Turning a cuboid
+/// on or off first cancels out the cubes in it that are already counted, by counting each
+/// intersection with a counted cuboid the opposite number of times. Intersections that are the
+/// same cuboid are counted together, which keeps the number of cuboids down.
+#[derive(Debug, Clone, Default)]
+pub struct Reactor {
+    counts: HashMap<Cuboid, i64>,
+}
+
+impl Reactor {
+    pub fn apply(&mut self, step: &Step) {
+        let mut changes: HashMap<Cuboid, i64> = HashMap::new();
+        for (cuboid, count) in &self.counts {
+            if let Some(intersection) = cuboid.intersection(&step.cuboid) {
+                *changes.entry(intersection).or_default() -= count;
+            }
+        }
+        if step.on {
+            *changes.entry(step.cuboid.clone()).or_default() += 1;
+        }
+
+        for (cuboid, change) in changes {
+            let count = self.counts.entry(cuboid).or_default();
+            *count += change;
+        }
+        self.counts.retain(|_, count| *count != 0);
+    }
+
+    /// The number of cubes that are on, or `None` if there are too many to count
+    pub fn on(&self) -> Option<u64> {
+        // a cuboid counted many times can have more cubes than fit in a u64, even if fewer
+        // are on in the end
+        let on = self.counts.iter().try_fold(0_i128, |on, (cuboid, count)| {
+            on.checked_add(cuboid.volume()?.checked_mul(*count as i128)?)
+        })?;
+        u64::try_from(on).ok()
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+    type Input = Vec<Step>;
+    type Part1 = Checked<u64>;
+    type Part2 = Checked<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, Step::from_str)
+    }
+
+    fn part_1(steps: &Self::Input) -> Self::Part1 {
+        let region = Cuboid::around_origin(INITIALIZATION);
+        let mut reactor = Reactor::default();
+        for step in steps {
+            if let Some(cuboid) = step.cuboid.intersection(&region) {
+                reactor.apply(&Step {
+                    on: step.on,
+                    cuboid,
+                });
+            }
+        }
+        reactor.on().into()
+    }
+
+    fn part_2(steps: &Self::Input) -> Self::Part2 {
+        let mut reactor = Reactor::default();
+        for step in steps {
+            reactor.apply(step);
+        }
+        reactor.on().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
+
+    const SMALL_EXAMPLE: &str = "\
+on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+    const EXAMPLE: &str = "\
+on x=-5..47,y=-31..22,z=-19..33
+on x=-44..5,y=-27..21,z=-14..35
+on x=-49..-1,y=-11..42,z=-10..38
+on x=-20..34,y=-40..6,z=-44..1
+off x=26..39,y=40..50,z=-2..11
+on x=-41..5,y=-41..6,z=-36..8
+off x=-43..-33,y=-45..-28,z=7..25
+on x=-33..15,y=-32..19,z=-34..11
+off x=35..47,y=-46..-34,z=-11..5
+on x=-14..36,y=-6..44,z=-16..29
+on x=-57795..-6158,y=29564..72030,z=20435..90618
+on x=36731..105352,y=-21140..28532,z=16094..90401
+on x=30999..107136,y=-53464..15513,z=8553..71215
+on x=13528..83982,y=-99403..-27377,z=-24141..23996
+on x=-72682..-12347,y=18159..111354,z=7391..80950
+on x=-1060..80757,y=-65301..-20884,z=-103788..-16709
+on x=-83015..-9461,y=-72160..-8347,z=-81239..-26856
+on x=-52752..22273,y=-49450..9096,z=54442..119054
+on x=-29982..40483,y=-108474..-28371,z=-24328..38471
+on x=-4958..62750,y=40422..118853,z=-7672..65583
+on x=55694..108686,y=-43367..46958,z=-26781..48729
+on x=-98497..-18186,y=-63569..3412,z=1232..88485
+on x=-726..56291,y=-62629..13224,z=18033..85226
+on x=-110886..-34664,y=-81338..-8658,z=8914..63723
+on x=-55829..24974,y=-16897..54165,z=-121762..-28058
+on x=-65152..-11147,y=22489..91432,z=-58782..1780
+on x=-120100..-32970,y=-46592..27473,z=-11695..61039
+on x=-18631..37533,y=-124565..-50804,z=-35667..28308
+on x=-57817..18248,y=49321..117703,z=5745..55881
+on x=14781..98692,y=-1341..70827,z=15753..70151
+on x=-34419..55919,y=-19626..40991,z=39015..114138
+on x=-60785..11593,y=-56135..2999,z=-95368..-26915
+on x=-32178..58085,y=17647..101866,z=-91405..-8878
+on x=-53655..12091,y=50097..105568,z=-75335..-4862
+on x=-111166..-40997,y=-71714..2688,z=5609..50954
+on x=-16602..70118,y=-98693..-44401,z=5197..76897
+on x=16383..101554,y=4615..83635,z=-44907..18747
+off x=-95822..-15171,y=-19987..48940,z=10804..104439
+on x=-89813..-14614,y=16069..88491,z=-3297..45228
+on x=41075..99376,y=-20427..49978,z=-52012..13762
+on x=-21330..50085,y=-17944..62733,z=-112280..-30197
+on x=-16478..35915,y=36008..118594,z=-7885..47086
+off x=-98156..-27851,y=-49952..43171,z=-99005..-8456
+off x=2032..69770,y=-71013..4824,z=7471..94418
+on x=43670..120875,y=-42068..12382,z=-24787..38892
+off x=37514..111226,y=-45862..25743,z=-16714..54663
+off x=25699..97951,y=-30668..59918,z=-15349..69697
+off x=-44271..17935,y=-9516..60759,z=49131..112598
+on x=-61695..-5813,y=40978..94975,z=8655..80240
+off x=-101086..-9439,y=-7088..67543,z=33935..83858
+off x=18020..114017,y=-48931..32606,z=21474..89843
+off x=-77139..10506,y=-89994..-18797,z=-80..59318
+off x=8476..79288,y=-75520..11602,z=-96624..-24783
+on x=-47488..-1262,y=24338..100707,z=16292..72967
+off x=-84341..13987,y=2429..92914,z=-90671..-1318
+off x=-37810..49457,y=-71013..-7894,z=-105357..-13188
+off x=-27365..46395,y=31009..98017,z=15428..76570
+off x=-70369..-16548,y=22648..78696,z=-1892..86821
+on x=-53470..21291,y=-120233..-33476,z=-44150..38147
+off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
+
+    #[test]
+    fn intersects_cuboids() {
+        let a = Cuboid::from_str("x=10..12,y=10..12,z=10..12").unwrap();
+        let b = Cuboid::from_str("x=11..13,y=11..13,z=11..13").unwrap();
+        let c = Cuboid::from_str("x=13..14,y=10..12,z=10..12").unwrap();
+        assert_eq!(
+            a.intersection(&b),
+            Some(Cuboid::from_str("x=11..12,y=11..12,z=11..12").unwrap())
+        );
+        assert_eq!(a.intersection(&c), None);
+        assert_eq!(a.to_string(), "x=10..12,y=10..12,z=10..12");
+        assert_eq!(a.volume(), Some(27));
+    }
+
+    #[test]
+    fn counts_cubes_turned_on() {
+        let steps = Day22::parse(SMALL_EXAMPLE).unwrap();
+        let mut reactor = Reactor::default();
+        let on: Vec<_> = steps
+            .iter()
+            .map(|step| {
+                reactor.apply(step);
+                reactor.on().unwrap()
+            })
+            .collect();
+        assert_eq!(on, [27, 46, 38, 39]);
+    }
+
+    #[test]
+    fn part_1_counts_cubes_in_the_initialization_region() {
+        let steps = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(Day22::part_1(&steps), Checked::Answer(474140));
+    }
+
+    #[test]
+    fn part_2_counts_every_cube() {
+        let steps = Day22::parse(EXAMPLE).unwrap();
+        assert_eq!(Day22::part_2(&steps), Checked::Answer(2758514936282235));
+    }
+
+    #[test]
+    fn counts_cuboids_as_far_apart_as_coordinates_go() {
+        let steps = Day22::parse("on x=1..300000,y=1..300000,z=-300000..-1").unwrap();
+        assert_eq!(Day22::part_1(&steps), Checked::Answer(50 * 50 * 50));
+        assert_eq!(Day22::part_2(&steps), Checked::Answer(300_000_u64.pow(3)));
+
+        // 2⁶⁴ cubes, one more than a u64 counts
+        let steps = Day22::parse("on x=0..4294967295,y=0..4294967295,z=0..0").unwrap();
+        assert_eq!(Day22::part_2(&steps), Checked::Overflow);
+        let (min, max) = (i64::MIN, i64::MAX);
+        let everything = format!("on x={0}..{1},y={0}..{1},z={0}..{1}", min, max);
+        let steps = Day22::parse(&format!("{}\noff x=0..0,y=0..0,z=0..0", everything)).unwrap();
+        assert_eq!(Day22::part_1(&steps), Checked::Answer(101 * 101 * 101 - 1));
+        assert_eq!(Day22::part_2(&steps), Checked::Overflow);
+    }
+
+    #[test]
+    fn rejects_malformed_steps() {
+        let error = |input| Day22::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error("on x=10..12,y=10..12,z=10..12\nof x=1..2,y=1..2,z=1..2"),
+            "day 22, line 2, column 1: expected 'on' or 'off', found 'of'"
+        );
+        assert_eq!(
+            error("off x=1..2,y=1..2"),
+            "day 22, line 1, column 18: expected a range of z, e.g. 'z=10..12', found nothing"
+        );
+        assert_eq!(
+            error("on x=1..2,y=2..1,z=1..2"),
+            "day 22, line 1, column 11: expected a range from the lowest to the highest \
+             coordinate, found 'y=2..1'"
+        );
+        assert_eq!(
+            error("on x=1..2,y=1..2,z=1..9223372036854775808"),
+            "day 22, line 1, column 23: expected a coordinate, found '9223372036854775808'"
+        );
+        assert_eq!(
+            error("on x=1..2,y=1..2,z=1..2,w=1..2"),
+            "day 22, line 1, column 23: expected a coordinate, found '2,w=1..2'"
+        );
+    }
+
+    /// Turns each cube on or off in turn
+    fn simulate(steps: &[Step]) -> usize {
+        let mut on = HashSet::new();
+        for step in steps {
+            let Cuboid { x, y, z } = &step.cuboid;
+            for cube in x
+                .clone()
+                .flat_map(|x| y.clone().map(move |y| (x, y)))
+                .flat_map(|(x, y)| z.clone().map(move |z| (x, y, z)))
+            {
+                if step.on {
+                    on.insert(cube);
+                } else {
+                    on.remove(&cube);
+                }
+            }
+        }
+        on.len()
+    }
+
+    fn step() -> impl Strategy<Value = Step> {
+        let range = (-5..=5i64, 0..=4i64).prop_map(|(start, length)| start..=start + length);
+        (any::<bool>(), range.clone(), range.clone(), range).prop_map(|(on, x, y, z)| Step {
+            on,
+            cuboid: Cuboid { x, y, z },
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn counting_cuboids_matches_turning_each_cube(
+            steps in prop::collection::vec(step(), 0..12),
+        ) {
+            let mut reactor = Reactor::default();
+            for step in &steps {
+                reactor.apply(step);
+            }
+            prop_assert_eq!(reactor.on(), Some(simulate(&steps) as u64));
+        }
+    }
+}
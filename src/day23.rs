@@ -0,0 +1,505 @@
+use crate::animation::{Animation, Control};
+use crate::error::ParseError;
+use crate::solution::{Checked, Solution};
+use pathfinding::prelude::{build_path, dijkstra_partial};
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+/// The number of spaces in the hallway
+pub const HALLWAY: usize = 11;
+
+/// The number of amphipods each room holds once the diagram is unfolded for part 2
+pub const MAX_DEPTH: usize = 4;
+
+/// Shown in place of the moves when a burrow can't be organized
+const UNORGANIZABLE: &str = "the amphipods can't be organized";
+
+/// The rows of amphipods hidden in the folded diagram, which go between its two rows of rooms
+const UNFOLDED: [[Amphipod; 4]; 2] = [
+    [
+        Amphipod::Desert,
+        Amphipod::Copper,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+    ],
+    [
+        Amphipod::Desert,
+        Amphipod::Bronze,
+        Amphipod::Amber,
+        Amphipod::Copper,
+    ],
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Amphipod::Amber),
+            'B' => Some(Amphipod::Bronze),
+            'C' => Some(Amphipod::Copper),
+            'D' => Some(Amphipod::Desert),
+            _ => None,
+        }
+    }
+
+    /// The energy used to move one space
+    pub fn energy(self) -> usize {
+        match self {
+            Amphipod::Amber => 1,
+            Amphipod::Bronze => 10,
+            Amphipod::Copper => 100,
+            Amphipod::Desert => 1000,
+        }
+    }
+
+    /// The room the amphipod belongs in, from the left
+    pub fn room(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for Amphipod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Amphipod::Amber => 'A',
+            Amphipod::Bronze => 'B',
+            Amphipod::Copper => 'C',
+            Amphipod::Desert => 'D',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// The hallway space just outside a room
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// Whether a hallway space is just outside a room, so that amphipods can't stop on it
+fn is_door(space: usize) -> bool {
+    (0..4).map(door).any(|d| d == space)
+}
+
+/// Where every amphipod is. Each room is listed from the hallway down.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY],
+    rooms: [[Option<Amphipod>; MAX_DEPTH]; 4],
+    depth: usize,
+}
+
+impl Burrow {
+    /// Adds the two hidden rows of amphipods to a folded burrow
+    pub fn unfold(&self) -> Self {
+        let mut rooms = self.rooms;
+        for (r, room) in rooms.iter_mut().enumerate() {
+            *room = [
+                self.rooms[r][0],
+                Some(UNFOLDED[0][r]),
+                Some(UNFOLDED[1][r]),
+                self.rooms[r][1],
+            ];
+        }
+        Self {
+            hallway: self.hallway,
+            rooms,
+            depth: MAX_DEPTH,
+        }
+    }
+
+    pub fn is_organized(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(r, room)| {
+            room[..self.depth]
+                .iter()
+                .all(|a| a.map(Amphipod::room) == Some(r))
+        })
+    }
+
+    /// Whether a room holds only the amphipods that belong in it, if any
+    fn is_settled(&self, room: usize) -> bool {
+        self.rooms[room][..self.depth]
+            .iter()
+            .flatten()
+            .all(|a| a.room() == room)
+    }
+
+    /// The deepest empty space in a room, if the room is settled
+    fn vacancy(&self, room: usize) -> Option<usize> {
+        if !self.is_settled(room) {
+            return None;
+        }
+        self.rooms[room][..self.depth]
+            .iter()
+            .rposition(Option::is_none)
+    }
+
+    /// Whether the hallway is clear from one space towards another, not including the first
+    fn is_clear(&self, from: usize, to: usize) -> bool {
+        let between = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.hallway[between].iter().all(Option::is_none)
+    }
+
+    /// Every move an amphipod can make, with the energy it uses
+    pub fn moves(&self) -> Vec<(Burrow, usize)> {
+        for (h, amphipod) in self.hallway.iter().enumerate() {
+            let amphipod = match amphipod {
+                Some(a) => *a,
+                None => continue,
+            };
+            let room = amphipod.room();
+            if let Some(space) = self.vacancy(room) {
+                if self.is_clear(h, door(room)) {
+                    let mut burrow = *self;
+                    burrow.hallway[h] = None;
+                    burrow.rooms[room][space] = Some(amphipod);
+                    let spaces = h.abs_diff(door(room)) + space + 1;
+                    // moving home only clears the hallway, so it's never worse than another move
+                    return vec![(burrow, spaces * amphipod.energy())];
+                }
+            }
+        }
+
+        let mut moves = Vec::new();
+        for room in 0..self.rooms.len() {
+            if self.is_settled(room) {
+                continue;
+            }
+            let (space, amphipod) = match self.rooms[room][..self.depth]
+                .iter()
+                .enumerate()
+                .find_map(|(s, a)| a.map(|a| (s, a)))
+            {
+                Some(top) => top,
+                None => continue,
+            };
+            for h in 0..HALLWAY {
+                if is_door(h) || !self.is_clear(door(room), h) {
+                    continue;
+                }
+                let mut burrow = *self;
+                burrow.rooms[room][space] = None;
+                burrow.hallway[h] = Some(amphipod);
+                let spaces = space + 1 + h.abs_diff(door(room));
+                moves.push((burrow, spaces * amphipod.energy()));
+            }
+        }
+
+        moves
+    }
+
+    /// The burrow after each move of the cheapest way to organize it, with the energy used so far
+    pub fn organize(&self) -> Option<Vec<(Burrow, usize)>> {
+        let (parents, organized) = dijkstra_partial(self, Burrow::moves, Burrow::is_organized);
+        let steps = build_path(&organized?, &parents)
+            .into_iter()
+            .map(|burrow| {
+                (
+                    burrow,
+                    parents.get(&burrow).map_or(0, |(_, energy)| *energy),
+                )
+            })
+            .collect();
+        Some(steps)
+    }
+
+    pub fn least_energy(&self) -> Checked<usize> {
+        match self.organize() {
+            Some(steps) => Checked::Answer(steps.last().map_or(0, |(_, e)| *e)),
+            None => Checked::NoAnswer(IMPOSSIBLE),
+        }
+    }
+
+    /// Animates the amphipods organizing themselves, one move per frame
+    pub fn animate(&self, animation: &mut Animation) -> io::Result<()> {
+        let steps = match self.organize() {
+            Some(steps) => steps,
+            None => return animation.finish(UNORGANIZABLE, self),
+        };
+        for (i, (burrow, _)) in steps.iter().enumerate() {
+            let caption = Self::caption(&steps, i);
+            if i + 1 == steps.len() {
+                return animation.finish(&caption, burrow);
+            }
+            if animation.show(&caption, burrow)? == Control::Quit {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the diagram after each move of the cheapest way to organize the burrow
+    pub fn write_moves(&self, out: &mut impl Write) -> io::Result<()> {
+        let steps = match self.organize() {
+            Some(steps) => steps,
+            None => return writeln!(out, "{}\n{}", UNORGANIZABLE, self),
+        };
+        for (i, (burrow, _)) in steps.iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "{}", Self::caption(&steps, i))?;
+            write!(out, "{}", burrow)?;
+        }
+        Ok(())
+    }
+
+    fn caption(steps: &[(Burrow, usize)], i: usize) -> String {
+        let total = steps.last().map_or(0, |(_, e)| *e);
+        format!(
+            "move {} of {}, energy used {} of {}",
+            i,
+            steps.len() - 1,
+            steps[i].1,
+            total
+        )
+    }
+}
+
+impl Display for Burrow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let space = |a: Option<Amphipod>| a.map_or(".".to_string(), |a| a.to_string());
+        writeln!(f, "#############")?;
+        let hallway: String = self.hallway.iter().map(|a| space(*a)).collect();
+        writeln!(f, "#{}#", hallway)?;
+        for row in 0..self.depth {
+            let (edge, end) = if row == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            let spaces: Vec<_> = self.rooms.iter().map(|room| space(room[row])).collect();
+            writeln!(f, "{}{}{}", edge, spaces.join("#"), end)?;
+        }
+        writeln!(f, "  #########")
+    }
+}
+
+/// The answer for a burrow that can't be organized
+const IMPOSSIBLE: &str = "impossible";
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+    type Input = Burrow;
+    type Part1 = Checked<usize>;
+    type Part2 = Checked<usize>;
+
+    /// Parses the folded diagram of a burrow with an empty hallway, with two of each amphipod
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        const DIAGRAM: [&str; 5] = [
+            "#############",
+            "#...........#",
+            "###?#?#?#?###",
+            "  #?#?#?#?#",
+            "  #########",
+        ];
+
+        let mut lines = input.lines();
+        let mut rooms = [[None; MAX_DEPTH]; 4];
+        let mut counts = [0; 4];
+        for (i, expected) in DIAGRAM.iter().enumerate() {
+            let line = lines.next().unwrap_or_default();
+            let error = |token, expected: &str| {
+                Err(ParseError::new(Self::DAY, line, token, expected).on_line(i + 1))
+            };
+            let mut chars = line.char_indices();
+            let mut room = 0;
+            for e in expected.chars() {
+                let expected = match e {
+                    '?' => "an amphipod, 'A', 'B', 'C' or 'D'".to_string(),
+                    _ => format!("'{}'", e),
+                };
+                let (x, c) = match chars.next() {
+                    Some(next) => next,
+                    None => return error(&line[line.len()..], &expected),
+                };
+                let token = &line[x..x + c.len_utf8()];
+                if e != '?' {
+                    if c != e {
+                        return error(token, &expected);
+                    }
+                    continue;
+                }
+
+                let amphipod = match Amphipod::from_char(c) {
+                    Some(a) => a,
+                    None => return error(token, &expected),
+                };
+                counts[amphipod.room()] += 1;
+                if counts[amphipod.room()] > 2 {
+                    return error(token, "two of each amphipod");
+                }
+                rooms[room][i - 2] = Some(amphipod);
+                room += 1;
+            }
+            if let Some((x, _)) = chars.next() {
+                return error(&line[x..], "the end of the line");
+            }
+        }
+        if let Some(l) = lines.next() {
+            return Err(ParseError::new(Self::DAY, l, l, "the end of the diagram").on_line(6));
+        }
+
+        Ok(Burrow {
+            hallway: [None; HALLWAY],
+            rooms,
+            depth: 2,
+        })
+    }
+
+    fn part_1(burrow: &Self::Input) -> Self::Part1 {
+        burrow.least_energy()
+    }
+
+    fn part_2(burrow: &Self::Input) -> Self::Part2 {
+        burrow.unfold().least_energy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########";
+
+    #[test]
+    fn unfolds_the_diagram() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(burrow.to_string(), format!("{}\n", EXAMPLE));
+        assert_eq!(
+            burrow.unfold().to_string(),
+            "\
+#############
+#...........#
+###B#C#B#D###
+  #D#C#B#A#
+  #D#B#A#C#
+  #A#D#C#A#
+  #########
+"
+        );
+    }
+
+    #[test]
+    fn moves_follow_the_rules() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        // each top amphipod can move to any of the 7 hallway spaces that aren't outside rooms
+        assert_eq!(burrow.moves().len(), 28);
+
+        let (burrow, energy) = burrow
+            .moves()
+            .into_iter()
+            .find(|(b, _)| b.hallway[3] == Some(Amphipod::Bronze) && b.rooms[2][0].is_none())
+            .unwrap();
+        assert_eq!(energy, 40);
+        // the bronze amphipod can't get into its room until the copper one has left it
+        assert!(burrow.moves().iter().all(|(b, _)| b.hallway[3].is_some()));
+    }
+
+    #[test]
+    fn organizes_with_the_least_energy() {
+        let steps = Day23::parse(EXAMPLE).unwrap().organize().unwrap();
+        let (last, energy) = steps.last().unwrap();
+        assert!(last.is_organized());
+        assert_eq!(*energy, 12521);
+        assert_eq!(
+            last.to_string(),
+            "\
+#############
+#...........#
+###A#B#C#D###
+  #A#B#C#D#
+  #########
+"
+        );
+    }
+
+    #[test]
+    fn writes_each_move_with_the_energy_used_so_far() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        let steps = burrow.organize().unwrap();
+        let mut out = vec![];
+        burrow.write_moves(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let moves: Vec<_> = out.split("\n\n").collect();
+        assert_eq!(moves.len(), steps.len());
+        assert_eq!(
+            moves[0],
+            format!(
+                "move 0 of {}, energy used 0 of 12521\n{}",
+                steps.len() - 1,
+                EXAMPLE
+            )
+        );
+        for (lines, (burrow, energy)) in moves.iter().zip(&steps) {
+            let (caption, diagram) = lines.split_once('\n').unwrap();
+            assert!(caption.ends_with(&format!("energy used {} of 12521", energy)));
+            assert_eq!(diagram.trim_end(), burrow.to_string().trim_end());
+        }
+        assert!(moves[moves.len() - 1].ends_with("###A#B#C#D###\n  #A#B#C#D#\n  #########\n"));
+    }
+
+    #[test]
+    fn part_1_organizes_the_folded_burrow() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part_1(&burrow), Checked::Answer(12521));
+    }
+
+    #[test]
+    fn part_2_organizes_the_unfolded_burrow() {
+        let burrow = Day23::parse(EXAMPLE).unwrap();
+        assert_eq!(Day23::part_2(&burrow), Checked::Answer(44169));
+    }
+
+    #[test]
+    fn some_unfolded_burrows_cannot_be_organized() {
+        let burrow = Day23::parse(
+            &EXAMPLE
+                .replace("B#C#B#D", "A#A#D#B")
+                .replace("A#D#C#A", "C#C#D#B"),
+        )
+        .unwrap();
+        assert_eq!(Day23::part_1(&burrow), Checked::Answer(11493));
+        assert_eq!(Day23::part_2(&burrow), Checked::NoAnswer(IMPOSSIBLE));
+        assert_eq!(Day23::part_2(&burrow).to_string(), "impossible");
+    }
+
+    #[test]
+    fn rejects_other_diagrams() {
+        let error = |input: &str| Day23::parse(input).unwrap_err().to_string();
+        assert_eq!(
+            error(&EXAMPLE.replace("#A#D#C#A#", "#A#D#C#E#")),
+            "day 23, line 4, column 10: expected an amphipod, 'A', 'B', 'C' or 'D', found 'E'"
+        );
+        assert_eq!(
+            error(&EXAMPLE.replace("#A#D#C#A#", "#A#D#C#B#")),
+            "day 23, line 4, column 10: expected two of each amphipod, found 'B'"
+        );
+        assert_eq!(
+            error(&EXAMPLE.replace("#...........#", "#.....A.....#")),
+            "day 23, line 2, column 7: expected '.', found 'A'"
+        );
+        assert_eq!(
+            error("#############\n#...........#"),
+            "day 23, line 3, column 1: expected '#', found nothing"
+        );
+    }
+}
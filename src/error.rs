@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// An error parsing a day's puzzle input, pointing at the token that could not be parsed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub day: usize,
+    /// 1-based line number in the input
+    pub line: usize,
+    /// 1-based column in the line
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error for a token within a line. The token must be a slice of the line, so that
+    /// its column can be found. A missing token can be reported with the empty slice at the end of
+    /// the line. The line number defaults to 1; use [ParseError::on_line] to set it.
+    pub fn new(day: usize, line: &str, token: &str, expected: impl Into<String>) -> Self {
+        let start = line.as_ptr() as usize;
+        let offset = token.as_ptr() as usize;
+        let column = if offset >= start && offset <= start + line.len() {
+            line[..offset - start].chars().count() + 1
+        } else {
+            1
+        };
+
+        Self {
+            day,
+            line: 1,
+            column,
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Sets the 1-based line number of the error
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+
+        if self.token.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "'{}'", self.token)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a token within a line
+pub fn parse_token<T: FromStr>(
+    day: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(day, line, token, expected))
+}
+
+/// Parses each line of the input, setting the line number of any error
+pub fn parse_lines<'a, T, F>(input: &'a str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| f(l).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
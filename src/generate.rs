@@ -0,0 +1,1039 @@
+//! Random puzzle inputs, for trying the solutions on more than the bundled inputs.
+//!
+//! Each day has a generator that produces input its parser accepts and its solution can answer,
+//! such as bingo games that every board eventually wins. The same seed and size always produce
+//! the same input.
+
+use crate::day16::{Contents, Operator, Packet};
+use crate::day19::{self, Rotation};
+use crate::day22::{self, Cuboid, Step};
+use crate::day23::Day23;
+use crate::grid::{Coord, Grid};
+use crate::solution::Solution;
+use crate::{day11, day17, day18};
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The size of a generated input, as a number `n` and an optional second number `k`. What they
+/// measure depends on the day:
+///
+/// | day | n | k |
+/// |-----|---|---|
+/// | 1, 2, 10, 18 | lines | |
+/// | 3 | numbers | bits per number, up to 32 |
+/// | 4 | boards | rows and columns per board |
+/// | 5 | lines | width and height of the area |
+/// | 6, 7 | fish or crabs | largest crab position (day 7) |
+/// | 8 | entries | |
+/// | 9, 11, 15, 20 | grid width | grid height |
+/// | 12 | caves, including start and end | |
+/// | 13 | dots | folds, up to 14 |
+/// | 14 | polymer template length | elements |
+/// | 16 | packets, roughly | |
+/// | 17 | target's furthest x, up to 1000000 | target's lowest y below the launch, up to 1000000 |
+/// | 19 | scanners | beacons placed near each scanner, besides those it shares |
+/// | 21, 23 | (unused) | |
+/// | 22 | reboot steps | steps within the initialization region, which come first |
+///
+/// When `k` isn't given, the day's default is used.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Size {
+    pub n: usize,
+    pub k: Option<usize>,
+}
+
+impl Size {
+    pub const fn new(n: usize, k: usize) -> Self {
+        Self { n, k: Some(k) }
+    }
+
+    /// This size, with `k` taken from another size if it has none
+    fn or(self, default: Size) -> Self {
+        Self {
+            n: self.n,
+            k: self.k.or(default.k),
+        }
+    }
+
+    fn k(&self) -> usize {
+        self.k.unwrap_or(0)
+    }
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    /// Parses a size such as `100`, or `100x5` for both numbers
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let positive = |n: &str| match n.parse() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!(
+                "size must be positive numbers, e.g. 100 or 100x5, got '{}'",
+                s
+            )),
+        };
+
+        match s.split_once('x') {
+            Some((n, k)) => Ok(Self::new(positive(n)?, positive(k)?)),
+            None => Ok(Self {
+                n: positive(s)?,
+                k: None,
+            }),
+        }
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.k {
+            Some(k) => write!(f, "{}x{}", self.n, k),
+            None => write!(f, "{}", self.n),
+        }
+    }
+}
+
+struct Generator {
+    /// The size used when none is given, about the size of the bundled input
+    size: Size,
+    generate: fn(&mut StdRng, Size) -> String,
+}
+
+const fn generator(size: Size, generate: fn(&mut StdRng, Size) -> String) -> Generator {
+    Generator { size, generate }
+}
+
+const fn n(n: usize) -> Size {
+    Size { n, k: None }
+}
+
+/// Every day's generator, in day order
+const GENERATORS: [Generator; 23] = [
+    generator(n(2000), depths),
+    generator(n(1000), commands),
+    generator(Size::new(1000, 12), diagnostic_report),
+    generator(Size::new(100, 5), bingo),
+    generator(Size::new(500, 1000), vents),
+    generator(n(300), lanternfish),
+    generator(Size::new(1000, 2000), crabs),
+    generator(n(200), signal_patterns),
+    generator(Size::new(100, 100), heightmap),
+    generator(n(100), navigation_subsystem),
+    generator(Size::new(10, 10), octopuses),
+    generator(n(12), caves),
+    generator(Size::new(800, 12), transparent_paper),
+    generator(Size::new(20, 10), polymer),
+    generator(Size::new(100, 100), risk_levels),
+    generator(n(200), transmission),
+    generator(Size::new(240, 90), target_area),
+    generator(n(100), snailfish_numbers),
+    generator(Size::new(30, 6), scanner_reports),
+    generator(Size::new(100, 100), trench_map),
+    generator(n(0), starting_positions),
+    generator(Size::new(420, 20), reboot_steps),
+    generator(n(0), amphipods),
+];
+
+/// The size a day's input is generated at when none is given, or `None` if there is no such day
+pub fn default_size(day: usize) -> Option<Size> {
+    Some(GENERATORS.get(day.checked_sub(1)?)?.size)
+}
+
+/// Generates an input for a day from a seed, or `None` if there is no such day. A size without
+/// `k` uses the day's default `k`.
+pub fn generate(day: usize, seed: u64, size: Option<Size>) -> Option<String> {
+    let generator = GENERATORS.get(day.checked_sub(1)?)?;
+    let size = size.map_or(generator.size, |s| s.or(generator.size));
+    let mut rng = StdRng::seed_from_u64(seed);
+    Some((generator.generate)(&mut rng, size))
+}
+
+/// Day 1: depths that wander up and down from a starting depth
+fn depths(rng: &mut StdRng, size: Size) -> String {
+    let mut depth: i32 = rng.gen_range(100..200);
+    let mut lines = Vec::with_capacity(size.n);
+    for _ in 0..size.n {
+        lines.push(depth.to_string());
+        depth = (depth + rng.gen_range(-10..=20)).max(0);
+    }
+    lines.join("\n")
+}
+
+/// Day 2: commands that keep the aim small, so the answers stay well within range
+fn commands(rng: &mut StdRng, size: Size) -> String {
+    let mut aim = 0;
+    let mut lines = Vec::with_capacity(size.n);
+    for _ in 0..size.n {
+        let x = rng.gen_range(1..10);
+        let command = match rng.gen_range(0..3) {
+            0 => "forward",
+            1 if aim >= x => {
+                aim -= x;
+                "up"
+            }
+            _ if aim + x <= 20 => {
+                aim += x;
+                "down"
+            }
+            _ => "forward",
+        };
+        lines.push(format!("{} {}", command, x));
+    }
+    lines.join("\n")
+}
+
+/// Day 3: distinct binary numbers, all with the same number of bits
+fn diagnostic_report(rng: &mut StdRng, size: Size) -> String {
+    // up to 32 bits, so that the answers fit in 64
+    let bits = size.k().clamp(1, 32);
+    let n = size.n.min(1 << bits);
+    index::sample(rng, 1 << bits, n)
+        .iter()
+        .map(|value| format!("{:0width$b}", value, width = bits))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 4: square boards of distinct numbers. Every number that appears on a board is drawn at
+/// some point, so every board wins.
+fn bingo(rng: &mut StdRng, size: Size) -> String {
+    let k = size.k().max(1);
+    let range = (k * k * 4).max(100);
+    let width = (range - 1).to_string().len();
+
+    let mut numbers: Vec<usize> = (0..range).collect();
+    numbers.shuffle(rng);
+    let mut input = numbers
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+
+    for _ in 0..size.n {
+        input.push('\n');
+        let board = index::sample(rng, range, k * k).into_vec();
+        for row in board.chunks(k) {
+            let row: Vec<_> = row
+                .iter()
+                .map(|n| format!("{:>width$}", n, width = width))
+                .collect();
+            input.push('\n');
+            input.push_str(&row.join(" "));
+        }
+    }
+    input
+}
+
+/// Day 5: horizontal and vertical lines, with every third line a 45° diagonal
+fn vents(rng: &mut StdRng, size: Size) -> String {
+    let extent = size.k().max(2);
+    let mut lines = Vec::with_capacity(size.n);
+    for i in 0..size.n {
+        let length = rng.gen_range(1..extent);
+        let start = |rng: &mut StdRng| rng.gen_range(0..extent - length);
+        let across = |rng: &mut StdRng| rng.gen_range(0..extent);
+        let ((x1, x2), (y1, y2)) = if i % 3 == 0 {
+            let (x, y) = (start(rng), start(rng));
+            ((x, x + length), (y, y + length))
+        } else if rng.gen() {
+            let (x, y) = (start(rng), across(rng));
+            ((x, x + length), (y, y))
+        } else {
+            let (x, y) = (across(rng), start(rng));
+            ((x, x), (y, y + length))
+        };
+
+        // flipping either axis keeps diagonals at 45°, in any of the four directions
+        let (x1, x2) = if rng.gen() { (x2, x1) } else { (x1, x2) };
+        let (y1, y2) = if rng.gen() { (y2, y1) } else { (y1, y2) };
+        lines.push(format!("{},{} -> {},{}", x1, y1, x2, y2));
+    }
+    lines.join("\n")
+}
+
+/// Day 6: timers between 1 and 5, as in the puzzle's inputs
+fn lanternfish(rng: &mut StdRng, size: Size) -> String {
+    (0..size.n)
+        .map(|_| rng.gen_range(1..=5).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Day 7: crab positions from 0 up to `k`
+fn crabs(rng: &mut StdRng, size: Size) -> String {
+    (0..size.n)
+        .map(|_| rng.gen_range(0..=size.k()).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The segments of each digit, wired as in the puzzle's example display
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Day 8: displays with their wires mixed up, showing all ten digits in a random order followed
+/// by a four digit output
+fn signal_patterns(rng: &mut StdRng, size: Size) -> String {
+    let mut lines = Vec::with_capacity(size.n);
+    for _ in 0..size.n {
+        let mut wires: Vec<char> = "abcdefg".chars().collect();
+        wires.shuffle(rng);
+        let pattern = |digit: usize, rng: &mut StdRng| {
+            let mut segments: Vec<char> = DIGIT_SEGMENTS[digit]
+                .chars()
+                .map(|s| wires[(s as u8 - b'a') as usize])
+                .collect();
+            segments.shuffle(rng);
+            segments.into_iter().collect::<String>()
+        };
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        digits.shuffle(rng);
+        let patterns: Vec<_> = digits.into_iter().map(|d| pattern(d, rng)).collect();
+        let output: Vec<_> = (0..4)
+            .map(|_| {
+                let digit = rng.gen_range(0..10);
+                pattern(digit, rng)
+            })
+            .collect();
+        lines.push(format!("{} | {}", patterns.join(" "), output.join(" ")));
+    }
+    lines.join("\n")
+}
+
+/// Writes a grid of digits, one row per line
+fn digit_grid(rng: &mut StdRng, size: Size, mut digit: impl FnMut(&mut StdRng) -> u8) -> String {
+    let (width, height) = (size.n, size.k().max(1));
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| char::from(b'0' + digit(rng)))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 9: basins of around 40 locations, walled off from each other by 9s. Each basin grows
+/// around a random centre, and within it heights rise away from one low point, so that every
+/// location flows down to exactly one low point as the puzzle assumes.
+fn heightmap(rng: &mut StdRng, size: Size) -> String {
+    let (width, height) = (size.n.max(1), size.k().max(1));
+    let centres: Vec<Coord> = (0..(width * height / 40).max(1))
+        .map(|_| (rng.gen_range(0..width), rng.gen_range(0..height)))
+        .collect();
+    // each location joins the basin of the centre a breadth first search from every centre at
+    // once reaches it from first, roughly the nearest, in time linear in the size of the map
+    let mut nearest: Grid<Option<usize>> = Grid::new(width, height, None);
+    let mut queue = VecDeque::new();
+    for (i, &centre) in centres.iter().enumerate() {
+        if nearest[centre].is_none() {
+            nearest[centre] = Some(i);
+            queue.push_back(centre);
+        }
+    }
+    while let Some(coord) = queue.pop_front() {
+        for n in nearest.neighbours(coord).collect::<Vec<_>>() {
+            if nearest[n].is_none() {
+                nearest[n] = nearest[coord];
+                queue.push_back(n);
+            }
+        }
+    }
+    let basins = nearest.map(|b| b.unwrap());
+
+    // a location is a wall if the location right of or below it is in another basin, so that
+    // neighbouring basins never touch
+    let mut heights: Grid<Option<u8>> = Grid::from_fn(width, height, |(x, y)| {
+        let basin = basins[(x, y)];
+        let other = |coord| basins.get(coord).is_some_and(|&b| b != basin);
+        if other((x + 1, y)) || other((x, y + 1)) {
+            Some(9)
+        } else {
+            None
+        }
+    });
+
+    for start in basins.coords() {
+        if heights[start].is_some() {
+            continue;
+        }
+        // walls can split a basin in two, so find the locations actually connected to this one
+        let mut basin = vec![start];
+        let mut seen: HashSet<Coord> = basin.iter().copied().collect();
+        let mut i = 0;
+        while i < basin.len() {
+            for n in heights.neighbours(basin[i]) {
+                if heights[n].is_none() && seen.insert(n) {
+                    basin.push(n);
+                }
+            }
+            i += 1;
+        }
+
+        // every location is at least as high as the one it was reached from, and the low point's
+        // neighbours are higher than it, so only the low point is lower than all its neighbours
+        let low_point = *basin.choose(rng).unwrap();
+        heights[low_point] = Some(0);
+        let mut queue = VecDeque::from([low_point]);
+        while let Some(coord) = queue.pop_front() {
+            let from = heights[coord].unwrap();
+            let next: Vec<Coord> = heights
+                .neighbours(coord)
+                .filter(|&n| heights[n].is_none())
+                .collect();
+            for n in next {
+                let rise = from == 0 || rng.gen_bool(0.6);
+                heights[n] = Some((from + u8::from(rise)).min(8));
+                queue.push_back(n);
+            }
+        }
+    }
+
+    heights
+        .map(|h| h.unwrap())
+        .to_string()
+        .trim_end()
+        .to_string()
+}
+
+/// Day 10: lines of about 100 brackets, half of them corrupted and the rest incomplete
+fn navigation_subsystem(rng: &mut StdRng, size: Size) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+    let mut lines = Vec::with_capacity(size.n);
+    for i in 0..size.n {
+        let length = rng.gen_range(90..=110);
+        let mut line = String::with_capacity(length + 10);
+        let mut open = vec![];
+        while line.len() < length || open.is_empty() {
+            // completion scores grow fivefold with each unclosed bracket, so few are left open
+            if open.is_empty() || (open.len() < 16 && rng.gen_bool(0.55)) {
+                let pair = *PAIRS.choose(rng).unwrap();
+                line.push(pair.0);
+                open.push(pair);
+            } else {
+                line.push(open.pop().unwrap().1);
+            }
+        }
+
+        // the first line is always incomplete, so that there is a completion score
+        if i > 0 && rng.gen() {
+            let expected = open.last().unwrap().1;
+            let wrong = PAIRS.iter().map(|p| p.1).filter(|&c| c != expected);
+            line.push(*wrong.collect::<Vec<_>>().choose(rng).unwrap());
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// The most steps a generated day 11 grid takes for every octopus to flash at once
+const MAX_SYNCHRONIZE_STEPS: usize = 1000;
+
+/// Day 11: energy levels that all flash at once within 1000 steps. Most random grids never
+/// synchronize, so most octopuses start with the same energy level, and grids are generated
+/// until one does.
+fn octopuses(rng: &mut StdRng, size: Size) -> String {
+    loop {
+        let level = rng.gen_range(0..=9);
+        let input = digit_grid(rng, size, |rng| {
+            if rng.gen_bool(0.3) {
+                rng.gen_range(0..=9)
+            } else {
+                level
+            }
+        });
+
+        let mut grid: day11::Grid = input.parse().unwrap();
+        if (0..MAX_SYNCHRONIZE_STEPS).any(|_| grid.advance_step() == grid.octopus_count()) {
+            return input;
+        }
+    }
+}
+
+/// Day 12: a connected cave system where no two big caves are connected, so that the number of
+/// paths is finite
+fn caves(rng: &mut StdRng, size: Size) -> String {
+    let mut names: Vec<String> = vec!["start".to_string()];
+    let mut taken: HashSet<String> = ["start", "end"].iter().map(|n| n.to_string()).collect();
+    // names start with two letters, ignoring case, and get a letter longer each time every name
+    // of the current length is taken
+    let (mut letters, mut available) = (2, 26 * 26);
+    while names.len() < size.n.max(2) - 1 {
+        if names.len() - 1 == available {
+            letters += 1;
+            available += 26_usize.pow(letters);
+        }
+        let name: String = (0..letters)
+            .map(|_| rng.gen_range(b'a'..=b'z') as char)
+            .collect();
+        let name = if rng.gen_bool(0.3) {
+            name.to_uppercase()
+        } else {
+            name
+        };
+        if taken.insert(name.to_lowercase()) {
+            names.push(name);
+        }
+    }
+    names.push("end".to_string());
+
+    let big = |name: &str| name.chars().all(|c| c.is_ascii_uppercase());
+    let mut edges: Vec<(usize, usize)> = vec![];
+    let add_edge = |a: usize, b: usize, edges: &mut Vec<(usize, usize)>| {
+        let edge = (a.min(b), a.max(b));
+        if a != b && !(big(&names[a]) && big(&names[b])) && !edges.contains(&edge) {
+            edges.push(edge);
+        }
+    };
+
+    // joining each cave to one before it that it can be joined to connects them all
+    for cave in 1..names.len() {
+        let earlier: Vec<_> = (0..cave)
+            .filter(|&c| !(big(&names[c]) && big(&names[cave])))
+            .collect();
+        add_edge(cave, *earlier.choose(rng).unwrap(), &mut edges);
+    }
+    for _ in 0..names.len() {
+        let (a, b) = (rng.gen_range(0..names.len()), rng.gen_range(0..names.len()));
+        add_edge(a, b, &mut edges);
+    }
+
+    edges.shuffle(rng);
+    edges
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = if rng.gen() { (a, b) } else { (b, a) };
+            format!("{}-{}", names[a], names[b])
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 13: dots on a sheet folded `k` times, about half along each axis, down to a 40x6 code.
+/// Each fold is along the middle of the sheet, so the halves are the same size and no dot lies
+/// on a fold line.
+fn transparent_paper(rng: &mut StdRng, size: Size) -> String {
+    let folds = size.k().clamp(2, 14);
+    let (x_folds, y_folds) = (folds / 2, folds - folds / 2);
+
+    // positions from the last fold back to the first, each sheet being twice the size plus the
+    // fold line of the one it folds down to
+    let positions = |count: usize, last: usize| {
+        (0..count)
+            .scan(last, |p, _| {
+                let position = *p;
+                *p = *p * 2 + 1;
+                Some(position)
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut xs = positions(x_folds, 40);
+    let mut ys = positions(y_folds, 6);
+
+    // folds are interleaved at random, but along each axis they must go from largest to smallest
+    let mut order: Vec<char> = (0..x_folds)
+        .map(|_| 'x')
+        .chain((0..y_folds).map(|_| 'y'))
+        .collect();
+    order.shuffle(rng);
+    let instructions: Vec<(char, usize)> = order
+        .into_iter()
+        .map(|axis| {
+            let position = if axis == 'x' { xs.pop() } else { ys.pop() };
+            (axis, position.unwrap())
+        })
+        .collect();
+
+    // each dot starts in the folded code and is unfolded onto either half of each fold
+    let mut lines = Vec::with_capacity(size.n + folds + 1);
+    for _ in 0..size.n {
+        let (mut x, mut y) = (rng.gen_range(0..40), rng.gen_range(0..6));
+        for &(axis, position) in instructions.iter().rev() {
+            let c = if axis == 'x' { &mut x } else { &mut y };
+            if rng.gen() {
+                *c = position * 2 - *c;
+            }
+        }
+        lines.push(format!("{},{}", x, y));
+    }
+    lines.push(String::new());
+    lines.extend(
+        instructions
+            .into_iter()
+            .map(|(axis, position)| format!("fold along {}={}", axis, position)),
+    );
+    lines.join("\n")
+}
+
+/// Day 14: a template of `k` elements, with a rule for every pair of them
+fn polymer(rng: &mut StdRng, size: Size) -> String {
+    let mut alphabet: Vec<char> = ('A'..='Z').collect();
+    alphabet.shuffle(rng);
+    let elements = &alphabet[..size.k().clamp(1, 26)];
+
+    let template: String = (0..size.n)
+        .map(|_| *elements.choose(rng).unwrap())
+        .collect();
+    let mut lines = vec![template, String::new()];
+    for a in elements {
+        for b in elements {
+            lines.push(format!("{}{} -> {}", a, b, elements.choose(rng).unwrap()));
+        }
+    }
+    lines[2..].shuffle(rng);
+    lines.join("\n")
+}
+
+/// Day 15: risk levels from 1 to 9
+fn risk_levels(rng: &mut StdRng, size: Size) -> String {
+    digit_grid(rng, size, |rng| rng.gen_range(1..=9))
+}
+
+/// Sub-packets are nested no deeper than this
+const MAX_PACKET_DEPTH: usize = 8;
+
+/// Day 16: a transmission of about `n` packets, nested in random expressions whose values fit in
+/// 64 bits
+fn transmission(rng: &mut StdRng, size: Size) -> String {
+    let packet = expression(rng, size.n.max(1), 0);
+    let mut bits = vec![];
+    encode_packet(rng, &packet, &mut bits);
+    bits.resize(bits.len() + (4 - bits.len() % 4) % 4, false);
+    bits.chunks(4)
+        .map(|c| {
+            let digit = c.iter().fold(0, |d, &b| d << 1 | b as u32);
+            std::char::from_digit(digit, 16)
+                .unwrap()
+                .to_ascii_uppercase()
+        })
+        .collect()
+}
+
+/// An expression of up to `packets` packets
+fn expression(rng: &mut StdRng, packets: usize, depth: usize) -> Packet {
+    let version = rng.gen_range(0..8);
+    if packets == 1 || depth == MAX_PACKET_DEPTH {
+        // literals of varying lengths, so that some take several groups
+        let bits = rng.gen_range(1..=36);
+        return Packet {
+            version,
+            contents: Contents::Literal(rng.gen_range(0..1 << bits)),
+        };
+    }
+
+    let operator = Operator::from_type_id(*[0, 1, 2, 3, 5, 6, 7].choose(rng).unwrap()).unwrap();
+    let count = if operator.is_comparison() {
+        2
+    } else {
+        rng.gen_range(1..=(packets - 1).min(5))
+    };
+    let mut shares = vec![1; count];
+    for _ in count..packets.saturating_sub(1) {
+        shares[rng.gen_range(0..count)] += 1;
+    }
+
+    let sub_packets: Vec<_> = shares
+        .into_iter()
+        .map(|share| expression(rng, share, depth + 1))
+        .collect();
+    let packet = |operator| Packet {
+        version,
+        contents: Contents::Operator(operator, sub_packets.clone()),
+    };
+    // sums and products that overflow become the minimum of their sub-packets instead
+    match packet(operator).value() {
+        Some(_) => packet(operator),
+        None => packet(Operator::Minimum),
+    }
+}
+
+/// Appends the bits of a packet, choosing either length type for operators
+fn encode_packet(rng: &mut StdRng, packet: &Packet, bits: &mut Vec<bool>) {
+    let push = |bits: &mut Vec<bool>, value: u64, count: usize| {
+        bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+    };
+
+    push(bits, packet.version as u64, 3);
+    match &packet.contents {
+        Contents::Literal(value) => {
+            push(bits, 4, 3);
+            let groups = (64 - value.leading_zeros() as usize).div_ceil(4);
+            for i in (0..groups.max(1)).rev() {
+                push(bits, (i > 0) as u64, 1);
+                push(bits, value >> (i * 4) & 0xf, 4);
+            }
+        }
+        Contents::Operator(operator, packets) => {
+            push(bits, operator.type_id() as u64, 3);
+            let mut sub_packets = vec![];
+            for p in packets {
+                encode_packet(rng, p, &mut sub_packets);
+            }
+
+            if sub_packets.len() < 1 << 15 && rng.gen() {
+                push(bits, 0, 1);
+                push(bits, sub_packets.len() as u64, 15);
+            } else {
+                push(bits, 1, 1);
+                push(bits, packets.len() as u64, 11);
+            }
+            bits.extend(sub_packets);
+        }
+    }
+}
+
+/// Day 17: a target area up to a quarter as wide, and a third as high, as it is far from the
+/// launch position
+fn target_area(rng: &mut StdRng, size: Size) -> String {
+    let x_max = (size.n as i64).clamp(1, day17::MAX_SEARCH);
+    let x_min = x_max - rng.gen_range(0..=x_max / 4);
+    let y_min = -(size.k() as i64).clamp(1, day17::MAX_SEARCH);
+    let y_max = y_min + rng.gen_range(0..=-y_min / 3);
+    format!(
+        "target area: x={}..{}, y={}..{}",
+        x_min, x_max, y_min, y_max
+    )
+}
+
+/// Day 18: reduced snailfish numbers, each a pair
+fn snailfish_numbers(rng: &mut StdRng, size: Size) -> String {
+    (0..size.n.max(2))
+        .map(|_| snailfish_number(rng, 0))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn snailfish_number(rng: &mut StdRng, depth: usize) -> String {
+    if depth == day18::MAX_DEPTH || (depth > 0 && rng.gen_bool(0.3)) {
+        rng.gen_range(0..10).to_string()
+    } else {
+        format!(
+            "[{},{}]",
+            snailfish_number(rng, depth + 1),
+            snailfish_number(rng, depth + 1)
+        )
+    }
+}
+
+/// Day 19: scanners placed one at a time, each near one already placed with enough beacons
+/// between them to be aligned, and reporting every beacon in range in a random rotation
+fn scanner_reports(rng: &mut StdRng, size: Size) -> String {
+    let range = day19::RANGE;
+    let mut scanners = vec![(0, 0, 0)];
+    let mut beacons = HashSet::new();
+    let mut place = |rng: &mut StdRng, min: (i32, i32, i32), max: (i32, i32, i32), count| {
+        for _ in 0..count {
+            beacons.insert((
+                rng.gen_range(min.0..=max.0),
+                rng.gen_range(min.1..=max.1),
+                rng.gen_range(min.2..=max.2),
+            ));
+        }
+    };
+
+    for i in 0..size.n.max(1) {
+        let (x, y, z) = if i == 0 {
+            (0, 0, 0)
+        } else {
+            let (px, py, pz) = *scanners.choose(rng).unwrap();
+            // far enough apart that scanners mostly see beacons of their own
+            let mut offset = || {
+                let offset = rng.gen_range(range * 9 / 10..=range * 6 / 5);
+                if rng.gen() {
+                    offset
+                } else {
+                    -offset
+                }
+            };
+            let scanner = (px + offset(), py + offset(), pz + offset());
+            // the shared beacons are in range of both scanners
+            place(
+                rng,
+                (
+                    px.max(scanner.0) - range,
+                    py.max(scanner.1) - range,
+                    pz.max(scanner.2) - range,
+                ),
+                (
+                    px.min(scanner.0) + range,
+                    py.min(scanner.1) + range,
+                    pz.min(scanner.2) + range,
+                ),
+                day19::OVERLAP,
+            );
+            scanners.push(scanner);
+            scanner
+        };
+        place(
+            rng,
+            (x - range, y - range, z - range),
+            (x + range, y + range, z + range),
+            size.k(),
+        );
+    }
+
+    let rotations = Rotation::all();
+    let mut beacons: Vec<_> = beacons.into_iter().collect();
+    beacons.sort_unstable();
+    let mut reports = vec![];
+    for (i, (x, y, z)) in scanners.into_iter().enumerate() {
+        let rotation = rotations.choose(rng).unwrap();
+        let mut report: Vec<_> = beacons
+            .iter()
+            .map(|b| (b.0 - x, b.1 - y, b.2 - z))
+            .filter(|b| b.0.abs() <= range && b.1.abs() <= range && b.2.abs() <= range)
+            .map(|b| {
+                let (x, y, z) = rotation.apply(b);
+                format!("{},{},{}", x, y, z)
+            })
+            .collect();
+        report.shuffle(rng);
+        reports.push(format!("--- scanner {} ---\n{}", i, report.join("\n")));
+    }
+    reports.join("\n\n")
+}
+
+/// Day 20: a random algorithm that lights dark squares and darkens light ones, so that the
+/// background flips on each step, and a random image
+fn trench_map(rng: &mut StdRng, size: Size) -> String {
+    let pixel = |light| if light { '#' } else { '.' };
+    let mut algorithm: Vec<char> = (0..512).map(|_| pixel(rng.gen())).collect();
+    algorithm[0] = '#';
+    algorithm[511] = '.';
+
+    let mut lines = vec![algorithm.into_iter().collect(), String::new()];
+    for _ in 0..size.k().max(1) {
+        lines.push((0..size.n.max(1)).map(|_| pixel(rng.gen())).collect());
+    }
+    lines.join("\n")
+}
+
+/// Day 21: a random starting position for each player
+fn starting_positions(rng: &mut StdRng, _: Size) -> String {
+    (1..=2)
+        .map(|player| {
+            let position = rng.gen_range(1..=10);
+            format!("Player {} starting position: {}", player, position)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 22: small cuboids in the initialization region, then large ones outside it reaching up to
+/// 200000 from the origin, mostly turned on
+fn reboot_steps(rng: &mut StdRng, size: Size) -> String {
+    let region = Cuboid::around_origin(day22::INITIALIZATION);
+    let initialization = size.k().min(size.n);
+    (0..size.n)
+        .map(|i| {
+            let (on, reach, length) = if i < initialization {
+                (rng.gen_bool(0.7), day22::INITIALIZATION, 1..=40)
+            } else {
+                (rng.gen_bool(0.8), 200_000, 20_000..=100_000)
+            };
+            let mut range = || {
+                let length = rng.gen_range(length.clone());
+                let start = rng.gen_range(-reach..=reach - length + 1);
+                start..=start + length - 1
+            };
+            let mut cuboid = Cuboid {
+                x: range(),
+                y: range(),
+                z: range(),
+            };
+            while i >= initialization && cuboid.intersection(&region).is_some() {
+                cuboid.x = range();
+            }
+            Step { on, cuboid }.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Day 23: the amphipods in a random order, which can be organized once the diagram is unfolded,
+/// as not all orders can
+fn amphipods(rng: &mut StdRng, _: Size) -> String {
+    let mut amphipods = *b"AABBCCDD";
+    loop {
+        amphipods.shuffle(rng);
+        let row = |r: &[u8]| {
+            let r: Vec<_> = r.iter().map(|&a| (a as char).to_string()).collect();
+            r.join("#")
+        };
+        let diagram = format!(
+            "#############\n#...........#\n###{}###\n  #{}#\n  #########",
+            row(&amphipods[..4]),
+            row(&amphipods[4..])
+        );
+
+        let burrow = Day23::parse(&diagram).unwrap();
+        if !burrow.is_organized() && burrow.unfold().least_energy().answer().is_some() {
+            return diagram;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day09;
+    use crate::day12::Day12;
+    use crate::solution::Solution;
+    use crate::PUZZLES;
+
+    /// Sizes small enough to solve quickly in debug builds
+    const SMALL: [Size; 23] = [
+        n(200),
+        n(200),
+        Size::new(200, 10),
+        Size::new(20, 5),
+        Size::new(100, 50),
+        n(50),
+        Size::new(100, 200),
+        n(20),
+        Size::new(30, 20),
+        n(20),
+        Size::new(10, 10),
+        n(8),
+        Size::new(100, 6),
+        Size::new(10, 5),
+        Size::new(20, 20),
+        n(50),
+        Size::new(30, 10),
+        n(10),
+        Size::new(5, 10),
+        Size::new(10, 10),
+        n(0),
+        Size::new(30, 10),
+        n(0),
+    ];
+
+    #[test]
+    fn generated_inputs_can_be_solved() {
+        for seed in 0..3 {
+            for (day, size) in (1..).zip(SMALL) {
+                let input = generate(day, seed, Some(size)).unwrap();
+                if let Err(e) = PUZZLES[day - 1].solve(&input, None) {
+                    panic!("seed {} {}\n{}", seed, e, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn the_same_seed_generates_the_same_input() {
+        for day in 1..=GENERATORS.len() {
+            assert_eq!(generate(day, 7, None), generate(day, 7, None));
+        }
+        assert_ne!(generate(4, 1, None), generate(4, 2, None));
+        assert_eq!(generate(24, 1, None), None);
+    }
+
+    #[test]
+    fn bingo_has_n_boards_of_size_k() {
+        let input = generate(4, 0, Some(Size::new(3, 7))).unwrap();
+        let boards: Vec<&str> = input.split("\n\n").skip(1).collect();
+        assert_eq!(boards.len(), 3);
+        for board in boards {
+            assert_eq!(board.lines().count(), 7);
+            assert!(board.lines().all(|l| l.split_whitespace().count() == 7));
+        }
+    }
+
+    #[test]
+    fn vents_include_diagonals() {
+        let input = generate(5, 0, Some(Size::new(30, 20))).unwrap();
+        let mut diagonals = 0;
+        for line in input.lines() {
+            let coords: Vec<i32> = line
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|c| !c.is_empty())
+                .map(|c| c.parse().unwrap())
+                .collect();
+            let (dx, dy) = ((coords[0] - coords[2]).abs(), (coords[1] - coords[3]).abs());
+            assert!(dx == 0 || dy == 0 || dx == dy, "{}", line);
+            if dx != 0 && dy != 0 {
+                diagonals += 1;
+            }
+        }
+        assert_eq!(diagonals, 10);
+    }
+
+    #[test]
+    fn every_basin_has_exactly_one_low_point() {
+        for seed in 0..5 {
+            let input = generate(9, seed, None).unwrap();
+            let map: day09::Map = input.parse().unwrap();
+            // basins of different low points never overlap, and between them cover every
+            // location that isn't a 9, so no basin has two low points and none has none
+            let mut flowed = HashSet::new();
+            for low_point in map.low_points() {
+                for point in low_point.basin(&map) {
+                    assert!(flowed.insert(point.coord()), "{:?}", point.coord());
+                }
+            }
+            let locations = map.points().iter().filter(|(_, p)| p.height() != 9);
+            assert_eq!(flowed.len(), locations.count());
+        }
+    }
+
+    #[test]
+    fn big_caves_are_never_connected() {
+        for seed in 0..10 {
+            let input = generate(12, seed, None).unwrap();
+            for line in input.lines() {
+                let (a, b) = line.split_once('-').unwrap();
+                let big = |c: &str| c.chars().all(|c| c.is_ascii_uppercase());
+                assert!(!(big(a) && big(b)), "{}", line);
+            }
+            assert!(Day12::parse(&input).is_ok());
+        }
+    }
+
+    #[test]
+    fn caves_get_longer_names_once_two_letters_run_out() {
+        let input = generate(12, 0, Some(n(26 * 26 + 10))).unwrap();
+        let mut names = HashSet::new();
+        for line in input.lines() {
+            let (a, b) = line.split_once('-').unwrap();
+            names.insert(a.to_lowercase());
+            names.insert(b.to_lowercase());
+        }
+        assert_eq!(names.len(), 26 * 26 + 10);
+        let three_letters = names.iter().filter(|n| n.len() == 3 && *n != "end");
+        assert_eq!(three_letters.count(), 8);
+    }
+
+    #[test]
+    fn folds_land_on_even_halves() {
+        let input = generate(13, 0, Some(Size::new(50, 5))).unwrap();
+        let (dots, folds) = input.split_once("\n\n").unwrap();
+        let (mut width, mut height) = (None, None);
+        for fold in folds.lines() {
+            let (axis, position) = fold[11..].split_once('=').unwrap();
+            let position: usize = position.parse().unwrap();
+            let size = if axis == "x" { &mut width } else { &mut height };
+            if let Some(s) = *size {
+                assert_eq!(s, position * 2 + 1);
+            }
+            *size = Some(position);
+        }
+        assert_eq!((width, height), (Some(40), Some(6)));
+        assert_eq!(dots.lines().count(), 50);
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!("100".parse(), Ok(n(100)));
+        assert_eq!("100x5".parse(), Ok(Size::new(100, 5)));
+        assert!("0".parse::<Size>().is_err());
+        assert!("10x".parse::<Size>().is_err());
+        assert_eq!(Size::new(3, 4).to_string(), "3x4");
+    }
+}
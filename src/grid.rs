@@ -0,0 +1,294 @@
+use crate::error::ParseError;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// An `(x, y)` coordinate in a [Grid], where `(0, 0)` is the top left
+pub type Coord = (usize, usize);
+
+const NEIGHBOURS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+const NEIGHBOURS_WITH_DIAGONALS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular 2D grid, stored row by row in a flat vec
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn<F: FnMut(Coord) -> T>(width: usize, height: usize, mut f: F) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with a row per line, converting each character with `f`. Rows must all be
+    /// the same length.
+    pub fn parse<F>(day: usize, input: &str, expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.char_indices() {
+                let value = f(c).ok_or_else(|| {
+                    ParseError::new(day, line, &line[x..x + c.len_utf8()], expected).on_line(y + 1)
+                })?;
+                cells.push(value);
+            }
+
+            let len = line.chars().count();
+            match width {
+                Some(w) if w != len => {
+                    let expected = format!("a row of {} cells", w);
+                    return Err(ParseError::new(day, line, line, expected).on_line(y + 1));
+                }
+                None if len == 0 => {
+                    return Err(ParseError::new(day, line, line, "a row of cells").on_line(y + 1))
+                }
+                _ => width = Some(len),
+            }
+        }
+
+        match width {
+            Some(width) => Ok(Self {
+                width,
+                height: cells.len() / width,
+                cells,
+            }),
+            None => Err(ParseError::new(day, input, input, "a grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): Coord) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self.cells[coord.1 * self.width + coord.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self.cells[coord.1 * self.width + coord.0])
+        } else {
+            None
+        }
+    }
+
+    /// All coordinates, row by row
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The up to 4 coordinates above, right, below and left of a coordinate that are in the grid
+    pub fn neighbours(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        self.offsets(coord, &NEIGHBOURS)
+    }
+
+    /// The up to 8 coordinates surrounding a coordinate, including diagonals, that are in the grid
+    pub fn neighbours_with_diagonals(&self, coord: Coord) -> impl Iterator<Item = Coord> {
+        self.offsets(coord, &NEIGHBOURS_WITH_DIAGONALS)
+    }
+
+    fn offsets(
+        &self,
+        (x, y): Coord,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Coord> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx)?;
+            let y = y.checked_add_signed(*dy)?;
+            if x < width && y < height {
+                Some((x, y))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single digits, with a row per line
+    pub fn parse_digits(day: usize, input: &str) -> Result<Self, ParseError> {
+        Self::parse(day, input, "a digit", |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord).expect("coordinate out of bounds")
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut(coord).expect("coordinate out of bounds")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::parse_digits(0, "123\n456\n789").unwrap()
+    }
+
+    #[test]
+    fn neighbours_stay_in_the_grid() {
+        let grid = grid();
+        let neighbours = |c| grid.neighbours(c).collect::<Vec<_>>();
+        assert_eq!(neighbours((0, 0)), [(1, 0), (0, 1)]);
+        assert_eq!(neighbours((2, 2)), [(2, 1), (1, 2)]);
+        assert_eq!(neighbours((1, 0)), [(2, 0), (1, 1), (0, 0)]);
+        assert_eq!(neighbours((1, 1)), [(1, 0), (2, 1), (1, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn neighbours_with_diagonals_stay_in_the_grid() {
+        let grid = grid();
+        let neighbours = |c| grid.neighbours_with_diagonals(c).collect::<Vec<_>>();
+        assert_eq!(neighbours((0, 0)), [(1, 0), (1, 1), (0, 1)]);
+        assert_eq!(neighbours((2, 0)), [(2, 1), (1, 1), (1, 0)]);
+        assert_eq!(neighbours((0, 1)), [(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+        assert_eq!(neighbours((1, 1)).len(), 8);
+    }
+
+    #[test]
+    fn parses_digits_row_by_row() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 3, 9));
+        assert_eq!(grid[(2, 0)], 3);
+        assert_eq!(grid[(0, 2)], 7);
+        assert_eq!(grid.rows().nth(1), Some(&[4, 5, 6][..]));
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_other_characters() {
+        let error = |input| Grid::parse_digits(3, input).unwrap_err().to_string();
+        assert_eq!(
+            error("123\n45\n789"),
+            "day 3, line 2, column 1: expected a row of 3 cells, found '45'"
+        );
+        assert_eq!(
+            error("123\n4x6"),
+            "day 3, line 2, column 2: expected a digit, found 'x'"
+        );
+        assert_eq!(
+            error("123\n\n456"),
+            "day 3, line 2, column 1: expected a row of 3 cells, found nothing"
+        );
+        assert_eq!(
+            error(""),
+            "day 3, line 1, column 1: expected a grid, found nothing"
+        );
+    }
+
+    #[test]
+    fn gets_only_coordinates_in_the_grid() {
+        let mut grid = grid();
+        assert_eq!(grid.get((2, 2)), Some(&9));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get_mut((3, 3)), None);
+        assert!(!grid.contains((usize::MAX, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate out of bounds")]
+    fn panics_indexing_out_of_bounds() {
+        let _ = grid()[(0, 3)];
+    }
+
+    #[test]
+    fn displays_a_row_per_line() {
+        assert_eq!(grid().to_string(), "123\n456\n789\n");
+        assert_eq!(Grid::new(2, 1, '#').to_string(), "##\n");
+    }
+}
@@ -0,0 +1,201 @@
+use crate::grid::Grid;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A colour, as red, green and blue components
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// Colours of the scale used for values, from lowest to highest
+const SCALE: [Rgb; 5] = [
+    Rgb(68, 1, 84),
+    Rgb(59, 82, 139),
+    Rgb(33, 145, 140),
+    Rgb(94, 201, 98),
+    Rgb(253, 231, 37),
+];
+
+/// Colours that are easy to tell apart, for things such as basins
+const PALETTE: [Rgb; 8] = [
+    Rgb(31, 119, 180),
+    Rgb(255, 127, 14),
+    Rgb(44, 160, 44),
+    Rgb(148, 103, 189),
+    Rgb(140, 86, 75),
+    Rgb(227, 119, 194),
+    Rgb(188, 189, 34),
+    Rgb(23, 190, 207),
+];
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 20, 20);
+
+    /// The colour of a value on a scale from 0 to max, from dark purple through blue and green
+    /// to yellow
+    pub fn scale(value: usize, max: usize) -> Self {
+        if max == 0 {
+            return SCALE[0];
+        }
+
+        let position = value.min(max) as f64 / max as f64 * (SCALE.len() - 1) as f64;
+        let i = (position as usize).min(SCALE.len() - 2);
+        SCALE[i].mix(SCALE[i + 1], position - i as f64)
+    }
+
+    /// One of a set of distinct colours, repeating after a few
+    pub fn palette(index: usize) -> Self {
+        PALETTE[index % PALETTE.len()]
+    }
+
+    /// Mixes in a fraction, from 0 to 1, of another colour
+    pub fn mix(self, other: Rgb, fraction: f64) -> Self {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * fraction).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+
+    /// The brightness of the colour as a shade of grey
+    pub fn grey(self) -> u8 {
+        (0.299 * self.0 as f64 + 0.587 * self.1 as f64 + 0.114 * self.2 as f64).round() as u8
+    }
+}
+
+/// Extra information that can be drawn over an image
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Overlay {
+    /// Day 9's low points
+    LowPoints,
+    /// Day 9's basins, each in its own colour
+    Basins,
+    /// Day 15's lowest risk path
+    Path,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    /// A binary colour bitmap
+    Ppm,
+    /// A binary greyscale bitmap
+    Pgm,
+    Svg,
+}
+
+impl Format {
+    /// The format for a file extension, such as `.svg`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(Format::Ppm),
+            "pgm" => Some(Format::Pgm),
+            "svg" => Some(Format::Svg),
+            _ => None,
+        }
+    }
+}
+
+/// Writes an image with each cell of the grid drawn as a square of `scale` pixels
+pub fn write<W: Write>(
+    image: &Grid<Rgb>,
+    format: Format,
+    scale: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    let scale = scale.max(1);
+    let (width, height) = (image.width() * scale, image.height() * scale);
+    match format {
+        Format::Ppm | Format::Pgm => {
+            let magic = if format == Format::Ppm { "P6" } else { "P5" };
+            write!(out, "{}\n{} {}\n255\n", magic, width, height)?;
+            for row in image.rows() {
+                let mut line = Vec::with_capacity(width * 3);
+                for rgb in row {
+                    for _ in 0..scale {
+                        match format {
+                            Format::Ppm => line.extend([rgb.0, rgb.1, rgb.2]),
+                            _ => line.push(rgb.grey()),
+                        }
+                    }
+                }
+                for _ in 0..scale {
+                    out.write_all(&line)?;
+                }
+            }
+        }
+        Format::Svg => {
+            writeln!(
+                out,
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" shape-rendering="crispEdges">"#,
+                width, height
+            )?;
+            for (y, row) in image.rows().enumerate() {
+                // a rect for each run of cells of the same colour keeps the file small
+                let mut x = 0;
+                for run in row.chunk_by(|a, b| a == b) {
+                    let Rgb(r, g, b) = run[0];
+                    writeln!(
+                        out,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="rgb({},{},{})"/>"#,
+                        x * scale,
+                        y * scale,
+                        run.len() * scale,
+                        scale,
+                        r,
+                        g,
+                        b
+                    )?;
+                    x += run.len();
+                }
+            }
+            writeln!(out, "</svg>")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_runs_from_the_first_to_the_last_colour() {
+        assert_eq!(Rgb::scale(0, 9), SCALE[0]);
+        assert_eq!(Rgb::scale(9, 9), SCALE[4]);
+        assert_eq!(Rgb::scale(20, 9), SCALE[4]);
+        assert_eq!(Rgb::scale(3, 0), SCALE[0]);
+    }
+
+    #[test]
+    fn writes_scaled_bitmaps() {
+        let image = Grid::from_fn(2, 1, |(x, _)| if x == 0 { Rgb::BLACK } else { Rgb::WHITE });
+
+        let mut ppm = vec![];
+        write(&image, Format::Ppm, 2, &mut ppm).unwrap();
+        let mut expected = b"P6\n4 2\n255\n".to_vec();
+        for _ in 0..2 {
+            expected.extend([0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255]);
+        }
+        assert_eq!(ppm, expected);
+
+        let mut pgm = vec![];
+        write(&image, Format::Pgm, 1, &mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\x00\xff");
+    }
+
+    #[test]
+    fn writes_a_rect_for_each_run_of_colour() {
+        let image = Grid::from_fn(3, 1, |(x, _)| if x < 2 { Rgb::RED } else { Rgb::WHITE });
+
+        let mut svg = vec![];
+        write(&image, Format::Svg, 10, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.contains(r#"<rect x="0" y="0" width="20" height="10" fill="rgb(220,20,20)"/>"#));
+        assert!(
+            svg.contains(r#"<rect x="20" y="0" width="10" height="10" fill="rgb(255,255,255)"/>"#)
+        );
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where to read a day's puzzle input from
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    Default(usize),
+    File(PathBuf),
+    Stdin,
+}
+
+/// The bundled input for a day, found from the crate's directory rather than the working directory
+pub fn default_path(day: usize) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("input/day_{}.txt", day))
+}
+
+impl Source {
+    /// Creates a source for a day from an optional path argument. No path reads the bundled
+    /// input and `-` reads standard input.
+    pub fn new(day: usize, path: Option<&str>) -> Self {
+        match path {
+            None => Source::Default(day),
+            Some("-") => Source::Stdin,
+            Some(p) => Source::File(PathBuf::from(p)),
+        }
+    }
+
+    /// Reads the input, trimming any trailing whitespace such as a final newline
+    pub fn read(&self) -> Result<String, InputError> {
+        let result = match self {
+            Source::Default(day) => fs::read_to_string(default_path(*day)),
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s).map(|_| s)
+            }
+        };
+
+        match result {
+            Ok(mut s) => {
+                s.truncate(s.trim_end().len());
+                Ok(s)
+            }
+            Err(error) => Err(InputError {
+                source: self.clone(),
+                error,
+            }),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default(day) => write!(f, "{}", default_path(*day).display()),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    source: Source,
+    error: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read input from {}: {}",
+            self.source, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Reads the input for a day from the first command line argument, if any
+pub fn from_args(day: usize) -> Result<String, InputError> {
+    let path = std::env::args().nth(1);
+    Source::new(day, path.as_deref()).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_bundled_input_from_the_crate_directory() {
+        assert!(default_path(1).is_absolute());
+        assert!(Source::new(1, None).read().unwrap().starts_with("134\n138"));
+        assert_eq!(
+            Source::new(1, Some("input/day_1.txt")),
+            Source::File(PathBuf::from("input/day_1.txt"))
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Solutions to [Advent of Code 2021](https://adventofcode.com/2021) puzzles.
+//!
+//! Each day is a module with the types used to solve it, and a [solution::Solution]
+//! implementation, such as [day12::Day12], that parses the input and answers both parts.
+
+pub mod animation;
+pub mod answers;
+pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod error;
+pub mod generate;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod runner;
+pub mod solution;
+
+use solution::Puzzle;
+
+/// Every day's solution, in day order
+pub const PUZZLES: [Puzzle; 23] = [
+    Puzzle::new::<day01::Day01>(),
+    Puzzle::new::<day02::Day02>(),
+    Puzzle::new::<day03::Day03>(),
+    Puzzle::new::<day04::Day04>(),
+    Puzzle::new::<day05::Day05>(),
+    Puzzle::new::<day06::Day06>(),
+    Puzzle::new::<day07::Day07>(),
+    Puzzle::new::<day08::Day08>(),
+    Puzzle::new::<day09::Day09>(),
+    Puzzle::new::<day10::Day10>(),
+    Puzzle::new::<day11::Day11>(),
+    Puzzle::new::<day12::Day12>(),
+    Puzzle::new::<day13::Day13>(),
+    Puzzle::new::<day14::Day14>(),
+    Puzzle::new::<day15::Day15>(),
+    Puzzle::new::<day16::Day16>(),
+    Puzzle::new::<day17::Day17>(),
+    Puzzle::new::<day18::Day18>(),
+    Puzzle::new::<day19::Day19>(),
+    Puzzle::new::<day20::Day20>(),
+    Puzzle::new::<day21::Day21>(),
+    Puzzle::new::<day22::Day22>(),
+    Puzzle::new::<day23::Day23>(),
+];
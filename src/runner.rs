@@ -0,0 +1,139 @@
+//! Runs days' solutions, one at a time or on a pool of worker threads, so that a day that fails,
+//! even by panicking, doesn't stop the others.
+
+use crate::error::ParseError;
+use crate::input::{InputError, Source};
+use crate::solution::{Answers, Part};
+use crate::PUZZLES;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, Once};
+use std::thread;
+
+/// Why a day couldn't be solved
+#[derive(Debug)]
+pub enum RunError {
+    Input(InputError),
+    Parse(ParseError),
+    /// The solution panicked, with the panic's message and location
+    Panic {
+        day: usize,
+        message: String,
+    },
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Input(e) => write!(f, "{}", e),
+            RunError::Parse(e) => write!(f, "{}", e),
+            RunError::Panic { day, message } => write!(f, "day {} panicked {}", day, message),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+/// Reads a day's input and solves both parts, or only the given part
+pub fn run_day(day: usize, source: &Source, part: Option<Part>) -> Result<Answers, RunError> {
+    let input = source.read().map_err(RunError::Input)?;
+    catch_panic(day, || PUZZLES[day - 1].solve(&input, part))?.map_err(RunError::Parse)
+}
+
+/// Solves each day from its bundled input on a number of worker threads, returning the results
+/// in the same order as the days
+pub fn run_parallel(
+    days: &[usize],
+    part: Option<Part>,
+    workers: usize,
+) -> Vec<(usize, Result<Answers, RunError>)> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(days.len()));
+    thread::scope(|s| {
+        for _ in 0..workers.clamp(1, days.len().max(1)) {
+            s.spawn(|| {
+                // each worker takes the next day that no other worker has started
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let result = run_day(day, &Source::Default(day), part);
+                    results.lock().unwrap().push((day, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(day, _)| days.iter().position(|d| d == day));
+    results
+}
+
+thread_local! {
+    /// Whether this thread is running a day, so that its panics are caught rather than printed
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The message and location of the last panic caught on this thread
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs a day's solution, turning a panic into an error. The panic is kept quiet, as it's
+/// reported as the error instead.
+fn catch_panic<T, F: FnOnce() -> T>(day: usize, f: F) -> Result<T, RunError> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                return default(info);
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("with a non-string payload");
+            let caught = match info.location() {
+                Some(l) => format!("at {}:{}:{}: {}", l.file(), l.line(), l.column(), message),
+                None => format!("with '{}'", message),
+            };
+            CAUGHT.with(|c| *c.borrow_mut() = Some(caught));
+        }));
+    });
+
+    CATCHING.with(|c| c.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(false));
+
+    result.map_err(|_| RunError::Panic {
+        day,
+        message: CAUGHT
+            .with(|c| c.borrow_mut().take())
+            .unwrap_or_else(|| "with no message".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_become_errors() {
+        let error = catch_panic(8, || -> usize { panic!("no digit") }).unwrap_err();
+        let message = error.to_string();
+        assert!(
+            message.starts_with("day 8 panicked at src/runner.rs:"),
+            "{}",
+            message
+        );
+        assert!(message.ends_with(": no digit"), "{}", message);
+        assert_eq!(catch_panic(8, || 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn reports_days_in_the_order_given() {
+        let results = run_parallel(&[3, 1, 2], Some(Part::One), 2);
+        let days: Vec<_> = results.iter().map(|(day, _)| *day).collect();
+        assert_eq!(days, [3, 1, 2]);
+        assert!(results.iter().all(|(_, r)| r.is_ok()));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::bench::{self, Timings};
+use crate::error::ParseError;
+use crate::input;
+use std::fmt::{Display, Formatter};
+use std::process;
+use std::time::{Duration, Instant};
+
+/// A solution to a day's puzzle. The input is parsed once, then shared by both parts.
+pub trait Solution {
+    const DAY: usize;
+
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_1(input: &Self::Input) -> Self::Part1;
+
+    fn part_2(input: &Self::Input) -> Self::Part2;
+}
+
+/// A part's answer. As well as the answer that is displayed, a part can report extra values that
+/// it found along the way, such as the index of day 4's winning board.
+pub trait Answer: Display {
+    fn extras(&self) -> Vec<(&'static str, i64)> {
+        Vec::new()
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+impl_answer!(i32, i64, u32, u64, usize, String);
+
+/// An answer for a part that some inputs have no answer to, or an answer too large to count.
+/// Parsing only checks that the input is well formed, so parts report these rather than panic.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Checked<T> {
+    Answer(T),
+    /// The answer, or a value on the way to it, is too large for the type it's counted in
+    Overflow,
+    /// The input has no answer, for the reason given, such as a bingo game that no board wins
+    NoAnswer(&'static str),
+}
+
+impl<T> Checked<T> {
+    /// The answer, if there is one
+    pub fn answer(&self) -> Option<&T> {
+        match self {
+            Checked::Answer(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// Maps the answer, keeping an overflow or the reason there's no answer
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Checked<U> {
+        match self {
+            Checked::Answer(answer) => Checked::Answer(f(answer)),
+            Checked::Overflow => Checked::Overflow,
+            Checked::NoAnswer(reason) => Checked::NoAnswer(reason),
+        }
+    }
+}
+
+/// An answer counted with checked arithmetic, where `None` means it overflowed
+impl<T> From<Option<T>> for Checked<T> {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Checked::Overflow, Checked::Answer)
+    }
+}
+
+impl<T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Checked::Answer(answer) => write!(f, "{}", answer),
+            Checked::Overflow => write!(f, "overflow"),
+            Checked::NoAnswer(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl<T: Answer> Answer for Checked<T> {
+    fn extras(&self) -> Vec<(&'static str, i64)> {
+        self.answer().map(T::extras).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A part's answer once solved
+#[derive(Debug, Clone, Default)]
+pub struct PartAnswer {
+    pub value: String,
+    pub extras: Vec<(&'static str, i64)>,
+    /// The time taken to solve the part, not including parsing the input
+    pub elapsed: Duration,
+}
+
+impl PartAnswer {
+    fn solve<A: Answer, F: FnOnce() -> A>(f: F) -> Self {
+        let start = Instant::now();
+        let answer = f();
+        let elapsed = start.elapsed();
+        Self {
+            value: answer.to_string(),
+            extras: answer.extras(),
+            elapsed,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub part_1: Option<PartAnswer>,
+    pub part_2: Option<PartAnswer>,
+}
+
+impl Answers {
+    /// Each part that was solved
+    pub fn parts(&self) -> impl Iterator<Item = (Part, &PartAnswer)> {
+        IntoIterator::into_iter([(Part::One, &self.part_1), (Part::Two, &self.part_2)])
+            .filter_map(|(part, answer)| answer.as_ref().map(|a| (part, a)))
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (part, answer) in self.parts() {
+            let value = &answer.value;
+            // multi-line answers, such as day 13's code, start on their own line
+            if value.contains('\n') {
+                writeln!(f, "part {}:\n{}", part, value.trim_end())?;
+            } else {
+                writeln!(f, "part {}: {}", part, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [Solution] with its types erased, so that all days can be run in the same way
+#[derive(Copy, Clone)]
+pub struct Puzzle {
+    day: usize,
+    solve: fn(&str, Option<Part>) -> Result<Answers, ParseError>,
+    bench: fn(&str, usize) -> Result<Timings, ParseError>,
+}
+
+impl Puzzle {
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            solve: solve::<S>,
+            bench: bench::bench::<S>,
+        }
+    }
+
+    pub fn day(&self) -> usize {
+        self.day
+    }
+
+    /// Solves both parts, or only the given part
+    pub fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, ParseError> {
+        (self.solve)(input, part)
+    }
+
+    /// Times parsing and both parts over a number of iterations
+    pub fn bench(&self, input: &str, iterations: usize) -> Result<Timings, ParseError> {
+        (self.bench)(input, iterations)
+    }
+}
+
+/// The `main` of each day's binary, which solves both parts of the input given as its first
+/// argument, or of the bundled input, and exits with an error if the input can't be read or parsed
+pub fn main<S: Solution>() {
+    let answers = input::from_args(S::DAY)
+        .map_err(|e| e.to_string())
+        .and_then(|input| solve::<S>(&input, None).map_err(|e| e.to_string()));
+
+    match answers {
+        Ok(answers) => print!("{}", answers),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Parses the input and solves both parts, or only the given part
+pub fn solve<S: Solution>(input: &str, part: Option<Part>) -> Result<Answers, ParseError> {
+    let input = S::parse(input)?;
+    Ok(Answers {
+        part_1: match part {
+            Some(Part::Two) => None,
+            _ => Some(PartAnswer::solve(|| S::part_1(&input))),
+        },
+        part_2: match part {
+            Some(Part::One) => None,
+            _ => Some(PartAnswer::solve(|| S::part_2(&input))),
+        },
+    })
+}